@@ -0,0 +1,531 @@
+use crate::domain::analysis::AnalysisWindow;
+use crate::domain::candles::Resolution;
+use crate::domain::history::HistoryLimit;
+use crate::domain::snipers::SniperOptions;
+use crate::domain::units::{LAMPORTS_PER_SOL, TOKEN_UNIT};
+use crate::services::bundles::BundleOptions;
+use crate::services::dev::DevOptions;
+use crate::domain::ledger::{CostMethod, LedgerSort};
+use crate::error::{self, Result};
+use crate::infra::rate_limit::{ProviderProfile, parse_method};
+use crate::infra::retry::RetryPolicy;
+use crate::infra::rpc_pool::PoolOptions;
+use crate::infra::tx_cache::CacheOptions;
+use crate::platforms::pumpfun::curve::{Amount, Fees, Side};
+use crate::services::preflight::AnalysisOptions;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Parser)]
+#[command(name = "solana_token_scanner", version, about = "Analyse Solana launchpad tokens")]
+pub struct Cli {
+    /// JSON-RPC endpoint(s); repeat the flag or separate with commas to build a failover pool
+    #[arg(long = "rpc-url", env = "RPC_URL", value_delimiter = ',', global = true, hide_env_values = true)]
+    pub rpc_urls: Vec<String>,
+
+    /// Commitment level used for RPC reads
+    #[arg(long, value_enum, default_value_t = CommitmentArg::Confirmed, global = true)]
+    pub commitment: CommitmentArg,
+
+    /// RPC provider profile used for rate limiting (default, public, helius, quicknode),
+    /// given in the same order as --rpc-url; the last one applies to the remaining endpoints
+    #[arg(long = "provider", default_value = "default", global = true)]
+    pub providers: Vec<String>,
+
+    /// Override the credits per second allowed by the provider profile
+    #[arg(long, global = true)]
+    pub rps: Option<f64>,
+
+    /// Override the number of concurrent requests allowed by the provider profile
+    #[arg(long, global = true)]
+    pub max_concurrent: Option<usize>,
+
+    /// Credit cost of an RPC method, e.g. `getTransaction=2` (repeatable)
+    #[arg(long = "rpc-weight", value_name = "METHOD=CREDITS", global = true)]
+    pub rpc_weights: Vec<String>,
+
+    /// Endpoints further behind than this many slots are only used as a last resort
+    #[arg(long, default_value_t = 50, global = true)]
+    pub max_slot_lag: u64,
+
+    /// Send a slow getTransaction to a second endpoint after this many milliseconds
+    #[arg(long, global = true)]
+    pub hedge_ms: Option<u64>,
+
+    /// Do not read or write the local transaction cache
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Ignore cached transactions and download them again (the cache is rewritten)
+    #[arg(long, global = true, conflicts_with = "no_cache")]
+    pub refresh: bool,
+
+    /// Directory of the local transaction cache
+    #[arg(long, env = "SCANNER_CACHE_DIR", default_value = ".scanner-cache", global = true)]
+    pub cache_dir: PathBuf,
+
+    /// Maximum size of the transaction cache in megabytes
+    #[arg(long, default_value_t = 2048, global = true)]
+    pub cache_max_mb: u64,
+
+    /// Record every RPC request and response into this fixture directory
+    #[arg(long, value_name = "DIR", global = true, conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Serve RPC responses from a fixture directory instead of the network
+    #[arg(long, value_name = "DIR", global = true)]
+    pub replay: Option<PathBuf>,
+
+    /// Output format of the report
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    pub output: OutputFormat,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+impl Cli {
+    pub fn provider_profile(&self, endpoint: usize) -> Result<ProviderProfile> {
+        let provider = self
+            .providers
+            .get(endpoint)
+            .or(self.providers.last())
+            .map(String::as_str)
+            .unwrap_or("default");
+        let mut profile = ProviderProfile::preset(provider).ok_or_else(|| {
+            error::Error::InvalidArgument(format!("unknown RPC provider `{provider}`"))
+        })?;
+        if let Some(rps) = self.rps {
+            profile.credits_per_second = rps;
+        }
+        if let Some(max_concurrent) = self.max_concurrent {
+            profile.max_concurrent = max_concurrent;
+        }
+        for weight in &self.rpc_weights {
+            let invalid = || error::Error::InvalidArgument(format!("invalid --rpc-weight `{weight}`"));
+            let (method, credits) = weight.split_once('=').ok_or_else(invalid)?;
+            let method = parse_method(method.trim()).ok_or_else(invalid)?;
+            let credits: u32 = credits.trim().parse().map_err(|_| invalid())?;
+            profile = profile.with_weight(method, credits);
+        }
+        Ok(profile)
+    }
+
+    pub fn cache_options(&self) -> Option<CacheOptions> {
+        // Enregistrement et rejeu doivent voir passer tous les appels RPC
+        let bypass = self.no_cache || self.record.is_some() || self.replay.is_some();
+        (!bypass).then(|| CacheOptions {
+            dir: self.cache_dir.clone(),
+            max_bytes: self.cache_max_mb.saturating_mul(1024 * 1024),
+            refresh: self.refresh,
+        })
+    }
+
+    // Même hôte que le premier endpoint RPC, en ws:// ou wss://
+    pub fn ws_url(&self, explicit: Option<&String>) -> Result<String> {
+        if let Some(url) = explicit {
+            return Ok(url.trim().to_string());
+        }
+        let rpc = self
+            .rpc_urls
+            .iter()
+            .map(|url| url.trim())
+            .find(|url| !url.is_empty())
+            .ok_or(error::Error::MissingRpcUrl)?;
+        if let Some(rest) = rpc.strip_prefix("https://") {
+            Ok(format!("wss://{rest}"))
+        } else if let Some(rest) = rpc.strip_prefix("http://") {
+            Ok(format!("ws://{rest}"))
+        } else {
+            Err(error::Error::InvalidArgument(format!(
+                "cannot derive a websocket url from `{rpc}`, pass --ws-url"
+            )))
+        }
+    }
+
+    pub fn pool_options(&self) -> PoolOptions {
+        PoolOptions {
+            max_slot_lag: self.max_slot_lag,
+            hedge_after: self.hedge_ms.map(Duration::from_millis),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Analyse a single mint
+    Scan {
+        mint: String,
+        #[command(flatten)]
+        options: ScanOptions,
+    },
+    /// Analyse many mints concurrently and summarise them in one table
+    ScanBatch {
+        /// File listing one mint per line; stdin when omitted or `-`
+        file: Option<PathBuf>,
+        /// Tokens analysed at the same time, all sharing the RPC rate limit
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+        /// Summary column the table is sorted on, e.g. total_volume_sol or buyers_count
+        #[arg(long, default_value = "total_volume_sol")]
+        sort: String,
+        /// Sort in ascending order instead of descending
+        #[arg(long)]
+        ascending: bool,
+        /// Write the summary to this file (.csv, .parquet or .ndjson)
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+        #[command(flatten)]
+        options: ScanOptions,
+    },
+    /// Follow new pump.fun launches live through the websocket logs subscription
+    Watch {
+        /// Websocket endpoint; derived from the first --rpc-url when omitted
+        #[arg(long, env = "WS_URL", hide_env_values = true)]
+        ws_url: Option<String>,
+        /// Program signatures replayed after a reconnection to recover missed launches (0 disables it)
+        #[arg(long, default_value_t = 500)]
+        backfill: usize,
+        /// Seconds without any log before the connection is considered dead
+        #[arg(long, default_value_t = 30)]
+        idle_timeout: u64,
+        /// Upper bound of the reconnection backoff, in seconds
+        #[arg(long, default_value_t = 30)]
+        max_reconnect_delay: u64,
+        /// Retries per transaction on rate limits, timeouts and 5xx errors
+        #[arg(long, default_value_t = 4)]
+        max_retries: u32,
+    },
+    /// Show the trades of one wallet on a mint
+    Wallet {
+        pubkey: String,
+        /// Mint whose trades are inspected
+        #[arg(long)]
+        mint: String,
+        #[command(flatten)]
+        options: ScanOptions,
+    },
+    /// Build OHLCV candles from the trades of a mint
+    Candles {
+        mint: String,
+        /// Candle width
+        #[arg(long, value_enum, default_value_t = ResolutionArg::OneMinute)]
+        resolution: ResolutionArg,
+        /// Write the candles to this file (.csv, .parquet or .ndjson) instead of printing them
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+        #[command(flatten)]
+        options: ScanOptions,
+    },
+    /// Export the decoded trades of a mint with their slot and block position
+    Trades {
+        mint: String,
+        /// Destination file (.parquet, .csv or .ndjson)
+        #[arg(long, value_name = "PATH")]
+        out: PathBuf,
+        #[command(flatten)]
+        options: ScanOptions,
+    },
+    /// Rank the wallets trading a mint by profit and loss
+    Pnl {
+        mint: String,
+        /// Cost basis used for realized and unrealized PnL
+        #[arg(long, value_enum, default_value_t = CostMethodArg::Fifo)]
+        method: CostMethodArg,
+        /// Column the table is sorted on, best first
+        #[arg(long, value_enum, default_value_t = LedgerSortArg::Total)]
+        sort: LedgerSortArg,
+        /// Number of wallets shown (all of them are written with --out)
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+        /// Write the table to this file (.csv, .parquet or .ndjson)
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+        #[command(flatten)]
+        options: ScanOptions,
+    },
+    /// Quote a buy or sell against the live pump.fun bonding curve
+    Quote {
+        mint: String,
+        #[arg(value_enum)]
+        side: QuoteSide,
+        /// SOL spent (buy) or received (sell), fees included
+        #[arg(long, required_unless_present = "tokens", conflicts_with = "tokens")]
+        sol: Option<f64>,
+        /// Whole tokens bought or sold
+        #[arg(long)]
+        tokens: Option<f64>,
+        /// Protocol fee in basis points
+        #[arg(long, default_value_t = Fees::default().fee_basis_points)]
+        fee_bps: u16,
+        /// Creator fee in basis points
+        #[arg(long, default_value_t = Fees::default().creator_fee_basis_points)]
+        creator_fee_bps: u16,
+    },
+    /// Snapshot the current holders of a mint and measure their concentration
+    Holders {
+        mint: String,
+        /// Number of holders listed
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+    /// List the other pump.fun tokens launched by a creator and score it as a serial rugger
+    Creator {
+        /// Creator wallet, or a pump.fun mint whose creator is looked up
+        address: String,
+        /// Creator signatures scanned for launches
+        #[arg(long, default_value_t = 1000)]
+        max_signatures: usize,
+        /// Launches whose outcome is rebuilt, most recent first
+        #[arg(long, default_value_t = 10)]
+        max_tokens: usize,
+        /// Transactions read per launched mint to find its trades, oldest first
+        #[arg(long, default_value_t = 200)]
+        token_signatures: usize,
+        /// Retries per transaction on rate limits, timeouts and 5xx errors
+        #[arg(long, default_value_t = 4)]
+        max_retries: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ResolutionArg {
+    #[value(name = "1s")]
+    OneSecond,
+    #[value(name = "15s")]
+    FifteenSeconds,
+    #[value(name = "1m")]
+    OneMinute,
+    #[value(name = "5m")]
+    FiveMinutes,
+    #[value(name = "1h")]
+    OneHour,
+}
+
+impl From<ResolutionArg> for Resolution {
+    fn from(value: ResolutionArg) -> Self {
+        match value {
+            ResolutionArg::OneSecond => Resolution::Seconds1,
+            ResolutionArg::FifteenSeconds => Resolution::Seconds15,
+            ResolutionArg::OneMinute => Resolution::Minutes1,
+            ResolutionArg::FiveMinutes => Resolution::Minutes5,
+            ResolutionArg::OneHour => Resolution::Hours1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CostMethodArg {
+    /// First in, first out
+    Fifo,
+    /// Average cost of the tokens held
+    Average,
+}
+
+impl From<CostMethodArg> for CostMethod {
+    fn from(value: CostMethodArg) -> Self {
+        match value {
+            CostMethodArg::Fifo => CostMethod::Fifo,
+            CostMethodArg::Average => CostMethod::Average,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LedgerSortArg {
+    Total,
+    Realized,
+    Unrealized,
+    Spent,
+}
+
+impl From<LedgerSortArg> for LedgerSort {
+    fn from(value: LedgerSortArg) -> Self {
+        match value {
+            LedgerSortArg::Total => LedgerSort::Total,
+            LedgerSortArg::Realized => LedgerSort::Realized,
+            LedgerSortArg::Unrealized => LedgerSort::Unrealized,
+            LedgerSortArg::Spent => LedgerSort::Spent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum QuoteSide {
+    Buy,
+    Sell,
+}
+
+impl From<QuoteSide> for Side {
+    fn from(value: QuoteSide) -> Self {
+        match value {
+            QuoteSide::Buy => Side::Buy,
+            QuoteSide::Sell => Side::Sell,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ScanOptions {
+    /// Which transactions of the history are analysed
+    #[arg(long, value_enum, default_value_t = WindowKind::First)]
+    pub window: WindowKind,
+
+    /// Number of transactions to analyse with the `first` and `last` windows
+    #[arg(long, default_value_t = 100)]
+    pub limit: usize,
+
+    /// Stop paginating the signature history after this many signatures
+    #[arg(long)]
+    pub max_signatures: Option<usize>,
+
+    /// Ignore signatures older than this slot (also bounds the `slots` window)
+    #[arg(long)]
+    pub min_slot: Option<u64>,
+
+    /// Ignore signatures newer than this slot (also bounds the `slots` window)
+    #[arg(long)]
+    pub max_slot: Option<u64>,
+
+    /// Ignore signatures older than this unix timestamp (also bounds the `time` window)
+    #[arg(long)]
+    pub since: Option<i64>,
+
+    /// Ignore signatures newer than this unix timestamp (also bounds the `time` window)
+    #[arg(long)]
+    pub until: Option<i64>,
+
+    /// Fail with an error when the history holds more signatures than this
+    #[arg(long)]
+    pub hard_cap: Option<usize>,
+
+    /// Retries per transaction on rate limits, timeouts and 5xx errors
+    #[arg(long, default_value_t = 4)]
+    pub max_retries: u32,
+
+    /// Buys landing within this many slots of the creation are flagged as snipes
+    #[arg(long, default_value_t = SniperOptions::default().max_slot_delay)]
+    pub sniper_slots: u64,
+
+    /// Number of non-sniper buys that make up the first retail wave
+    #[arg(long, default_value_t = SniperOptions::default().first_wave)]
+    pub first_wave: usize,
+
+    /// Blocks read to order transactions sharing a slot, oldest slots first (0 keeps fetch order)
+    #[arg(long, default_value_t = 20)]
+    pub block_lookups: usize,
+
+    /// Early buyers whose funding source is traced back
+    #[arg(long, default_value_t = BundleOptions::default().funder_lookups)]
+    pub funder_lookups: usize,
+
+    /// Creator wallet signatures scanned for token transfers out (0 skips the lookup)
+    #[arg(long, default_value_t = DevOptions::default().history)]
+    pub dev_history: usize,
+}
+
+impl ScanOptions {
+    pub fn analysis_options(&self, tx_config: RpcTransactionConfig) -> AnalysisOptions {
+        let window = match self.window {
+            WindowKind::First => AnalysisWindow::First(self.limit),
+            WindowKind::Last => AnalysisWindow::Last(self.limit),
+            WindowKind::Slots => AnalysisWindow::Slots { min: self.min_slot, max: self.max_slot },
+            WindowKind::Time => AnalysisWindow::Time { since: self.since, until: self.until },
+            WindowKind::Full => AnalysisWindow::Full,
+        };
+        AnalysisOptions {
+            tx_config,
+            window,
+            history: HistoryLimit {
+                max_signatures: self.max_signatures,
+                min_slot: self.min_slot,
+                max_slot: self.max_slot,
+                since: self.since,
+                until: self.until,
+                until_signature: None,
+                hard_cap: self.hard_cap,
+            },
+            retry: RetryPolicy {
+                max_retries: self.max_retries,
+                ..RetryPolicy::default()
+            },
+            block_lookups: self.block_lookups,
+            snipers: SniperOptions {
+                max_slot_delay: self.sniper_slots,
+                first_wave: self.first_wave,
+            },
+            bundles: BundleOptions {
+                funder_lookups: self.funder_lookups,
+            },
+            dev: DevOptions { history: self.dev_history },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum WindowKind {
+    /// First trades after creation
+    First,
+    /// Most recent trades
+    Last,
+    /// Every trade between --min-slot and --max-slot
+    Slots,
+    /// Every trade between --since and --until
+    Time,
+    /// The whole history
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CommitmentArg {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl From<CommitmentArg> for CommitmentConfig {
+    fn from(value: CommitmentArg) -> Self {
+        match value {
+            CommitmentArg::Processed => CommitmentConfig::processed(),
+            CommitmentArg::Confirmed => CommitmentConfig::confirmed(),
+            CommitmentArg::Finalized => CommitmentConfig::finalized(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Debug,
+    /// JSON documents instead of text; scan-batch and watch print one line per token
+    Json,
+}
+
+// Convertit --sol / --tokens en unités de base
+pub fn quote_amount(sol: Option<f64>, tokens: Option<f64>) -> Result<Amount> {
+    let invalid = |v: f64| error::Error::InvalidArgument(format!("invalid amount `{v}`"));
+    match (sol, tokens) {
+        (Some(sol), _) if sol.is_finite() && sol > 0.0 => Ok(Amount::Sol((sol * LAMPORTS_PER_SOL).round() as u64)),
+        (None, Some(tokens)) if tokens.is_finite() && tokens > 0.0 => Ok(Amount::Tokens(
+            (tokens * TOKEN_UNIT).round() as u64,
+        )),
+        (Some(v), _) | (None, Some(v)) => Err(invalid(v)),
+        (None, None) => Err(error::Error::InvalidArgument("pass --sol or --tokens".to_string())),
+    }
+}
+
+pub fn read_mint_list(path: Option<&PathBuf>) -> Result<Vec<String>> {
+    let content = match path {
+        Some(path) if path.as_os_str() != "-" => std::fs::read_to_string(path)?,
+        _ => std::io::read_to_string(std::io::stdin())?,
+    };
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
@@ -1,24 +1,127 @@
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use serde::Serialize;
 use solana_sdk::pubkey::Pubkey;
 use std::fmt::Display;
+use crate::domain::base58;
+use crate::domain::decoder::mint::MintInfo;
+use crate::domain::fetch::FetchFailure;
+use crate::domain::history::{HistoryLimit, HistorySummary};
+use crate::domain::bundles::BundleReport;
+use crate::domain::dev::DevReport;
+use crate::domain::snipers::SniperReport;
 use crate::platforms::platforms::Platform;
+use crate::platforms::pumpfun::accounts::BondingCurve;
+use crate::platforms::pumpfun::events::{CreateEnvelope, CreateEvent, TradeEnvelope};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisWindow {
+    First(usize),
+    Last(usize),
+    Slots { min: Option<u64>, max: Option<u64> },
+    Time { since: Option<i64>, until: Option<i64> },
+    Full,
+}
+
+impl AnalysisWindow {
+    // `statuses` est dans l'ordre RPC (plus récent d'abord), le résultat en ordre chronologique
+    pub fn select(&self, statuses: &[RpcConfirmedTransactionStatusWithSignature]) -> Vec<String> {
+        let chronological = statuses.iter().rev().filter(|s| s.err.is_none());
+        let selected: Vec<&RpcConfirmedTransactionStatusWithSignature> = match self {
+            AnalysisWindow::First(n) => chronological.take(*n).collect(),
+            AnalysisWindow::Last(n) => {
+                let all: Vec<_> = chronological.collect();
+                all[all.len().saturating_sub(*n)..].to_vec()
+            }
+            AnalysisWindow::Slots { min, max } => chronological
+                .filter(|s| min.is_none_or(|min| s.slot >= min) && max.is_none_or(|max| s.slot <= max))
+                .collect(),
+            AnalysisWindow::Time { since, until } => chronological
+                .filter(|s| match s.block_time {
+                    Some(t) => since.is_none_or(|since| t >= since) && until.is_none_or(|until| t <= until),
+                    None => false,
+                })
+                .collect(),
+            AnalysisWindow::Full => chronological.collect(),
+        };
+        selected.into_iter().map(|s| s.signature.clone()).collect()
+    }
+
+    // Évite de paginer au-delà de ce que la fenêtre peut retenir
+    pub fn narrow(&self, history: &mut HistoryLimit) {
+        match self {
+            AnalysisWindow::Slots { min, max } => {
+                history.min_slot = history.min_slot.max(*min);
+                history.max_slot = match (history.max_slot, *max) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+            AnalysisWindow::Time { since, until } => {
+                history.since = history.since.max(*since);
+                history.until = match (history.until, *until) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Display for AnalysisWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn bound<T: Display>(v: &Option<T>) -> String {
+            v.as_ref().map(|v| v.to_string()).unwrap_or_default()
+        }
+        match self {
+            AnalysisWindow::First(n) => write!(f, "first:{n}"),
+            AnalysisWindow::Last(n) => write!(f, "last:{n}"),
+            AnalysisWindow::Slots { min, max } => write!(f, "slots:{}..{}", bound(min), bound(max)),
+            AnalysisWindow::Time { since, until } => write!(f, "time:{}..{}", bound(since), bound(until)),
+            AnalysisWindow::Full => write!(f, "full"),
+        }
+    }
+}
+
+// Transaction de création : connue du preflight si l'historique est complet, sinon par le CreateEvent de la fenêtre
+#[derive(Debug, Clone, Serialize)]
+pub struct Creation {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
 pub struct TokenPreflight {
+    #[serde(serialize_with = "base58::pubkey")]
     pub token_address: Pubkey,
-    pub platform: Option<Platform>, 
+    pub mint: MintInfo,
+    pub platform: Option<Platform>,
+    pub window: AnalysisWindow,
     pub transactions_to_analyze: Vec<String>,
+    pub creation: Option<Creation>,
+    pub history: HistorySummary,
 }
 
 impl TokenPreflight {
     pub fn new(
         token_address: Pubkey,
-        platform: Option<Platform>, 
+        mint: MintInfo,
+        platform: Option<Platform>,
+        window: AnalysisWindow,
         transactions_to_analyze: Vec<String>,
+        creation: Option<Creation>,
+        history: HistorySummary,
     ) -> Self {
         Self {
             token_address,
+            mint,
             platform,
+            window,
             transactions_to_analyze,
+            creation,
+            history,
         }
     }
 }
@@ -27,10 +130,41 @@ impl Display for TokenPreflight {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "TokenPreflight(token_address={}, platform={:?}, transactions_to_analyze=[{:?}]",
+            "TokenPreflight(token_address={}, program={}, platform={:?}, window={}, signatures_fetched={}, pages_walked={}, history_complete={}, transactions_to_analyze=[{:?}]",
             self.token_address,
+            self.mint.program,
             self.platform,
+            self.window,
+            self.history.signatures_fetched,
+            self.history.pages,
+            self.history.complete,
             self.transactions_to_analyze.join(",")
         )
     }
 }
+
+#[derive(Debug)]
+pub struct TokenAnalysis {
+    pub preflight: TokenPreflight,
+    // Ordonnés par (slot, position dans le bloc), sans doublon
+    pub trades: Vec<TradeEnvelope>,
+    pub creates: Vec<CreateEnvelope>,
+    // État de la courbe au moment de l'analyse (pump.fun uniquement)
+    pub bonding_curve: Option<BondingCurve>,
+    pub snipers: SniperReport,
+    pub bundles: BundleReport,
+    // Comportement du créateur (pump.fun uniquement)
+    pub dev: Option<DevReport>,
+    // Signatures de la fenêtre qui n'ont pas pu être récupérées
+    pub failures: Vec<FetchFailure>,
+}
+
+impl TokenAnalysis {
+    pub fn create(&self) -> Option<&CreateEvent> {
+        self.creates.first().map(|c| &c.event)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
@@ -0,0 +1,35 @@
+use crate::error;
+use serde::Serializer;
+use solana_sdk::pubkey::{ParsePubkeyError, Pubkey};
+use std::fmt::Debug;
+use std::str::FromStr;
+
+// Adresse saisie par l'utilisateur (argument ou ligne de fichier), espaces ignorés
+pub fn parse_pubkey(address: &str) -> error::Result<Pubkey> {
+    Pubkey::from_str(address.trim()).map_err(|e| match e {
+        ParsePubkeyError::WrongSize => error::Error::WrongSizeToken(address.trim().len()),
+        ParsePubkeyError::Invalid => error::Error::InvalidAddress(address.to_string()),
+    })
+}
+
+// Le Serialize de Pubkey produit un tableau de 32 octets : on écrit la forme base58 des explorateurs
+
+pub fn pubkey<S: Serializer>(key: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(key)
+}
+
+pub fn pubkeys<S: Serializer>(keys: &[Pubkey], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(keys.iter().map(Pubkey::to_string))
+}
+
+pub fn option_pubkey<S: Serializer>(key: &Option<Pubkey>, serializer: S) -> Result<S::Ok, S::Error> {
+    match key {
+        Some(key) => serializer.collect_str(key),
+        None => serializer.serialize_none(),
+    }
+}
+
+// Types externes sans Serialize (ExtensionType), écrits sous leur nom Debug
+pub fn debug<T: Debug, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("{value:?}"))
+}
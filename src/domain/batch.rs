@@ -0,0 +1,174 @@
+use crate::domain::analysis::TokenAnalysis;
+use crate::domain::token_stats::TokenStats;
+use crate::error;
+use polars::prelude::*;
+
+// Ce qu'on garde d'une analyse réussie : le lot peut compter des centaines de tokens
+#[derive(Debug)]
+pub struct TokenSummary {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub complete: bool,
+    pub stats: TokenStats,
+}
+
+impl From<&TokenAnalysis> for TokenSummary {
+    fn from(analysis: &TokenAnalysis) -> Self {
+        let create = analysis.create();
+        Self {
+            name: create.map(|c| c.name.clone()),
+            symbol: create.map(|c| c.symbol.clone()),
+            complete: analysis.is_complete(),
+            stats: TokenStats::new(&analysis.trades),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BatchEntry {
+    pub mint: String,
+    // Message de l'erreur qui a interrompu l'analyse de ce token
+    pub outcome: Result<TokenSummary, String>,
+}
+
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    // Dans l'ordre d'arrivée des résultats
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchReport {
+    pub fn failed(&self) -> impl Iterator<Item = &BatchEntry> {
+        self.entries.iter().filter(|e| e.outcome.is_err())
+    }
+
+    // Une ligne par token ; les colonnes de TokenStats sont nulles pour les échecs
+    pub fn frame(&self) -> PolarsResult<DataFrame> {
+        let rows = &self.entries;
+        let stats: Vec<Option<&TokenStats>> = rows.iter().map(|e| e.outcome.as_ref().ok().map(|s| &s.stats)).collect();
+        let stat = |f: fn(&TokenStats) -> f64| stats.iter().map(|s| s.map(f)).collect::<Vec<_>>();
+        let count = |f: fn(&TokenStats) -> usize| stats.iter().map(|s| s.map(|s| f(s) as u64)).collect::<Vec<_>>();
+        let average = |f: fn(&TokenStats) -> Option<f64>| stats.iter().map(|s| s.and_then(f)).collect::<Vec<_>>();
+        df!(
+            "mint" => rows.iter().map(|e| e.mint.clone()).collect::<Vec<_>>(),
+            "status" => rows.iter().map(|e| if e.outcome.is_ok() { "ok" } else { "error" }).collect::<Vec<_>>(),
+            "error" => rows.iter().map(|e| e.outcome.as_ref().err().cloned()).collect::<Vec<_>>(),
+            "symbol" => rows.iter().map(|e| e.outcome.as_ref().ok().and_then(|s| s.symbol.clone())).collect::<Vec<_>>(),
+            "name" => rows.iter().map(|e| e.outcome.as_ref().ok().and_then(|s| s.name.clone())).collect::<Vec<_>>(),
+            "complete" => rows.iter().map(|e| e.outcome.as_ref().ok().map(|s| s.complete)).collect::<Vec<_>>(),
+            "total_trades" => count(|s| s.total_trades),
+            "total_volume_sol" => stat(|s| s.total_volume_sol),
+            "makers_count" => count(|s| s.makers_count),
+            "buy_count" => count(|s| s.buy_count),
+            "sell_count" => count(|s| s.sell_count),
+            "buy_volume_sol" => stat(|s| s.buy_volume_sol),
+            "sell_volume_sol" => stat(|s| s.sell_volume_sol),
+            "buyers_count" => count(|s| s.buyers_count),
+            "sellers_count" => count(|s| s.sellers_count),
+            "avg_trades_per_second" => average(|s| s.avg_trades_per_second),
+            "avg_trades_per_wallet" => average(|s| s.avg_trades_per_wallet),
+            "avg_volume_per_wallet_sol" => average(|s| s.avg_volume_per_wallet_sol),
+            "avg_volume_per_tx_sol" => average(|s| s.avg_volume_per_tx_sol),
+            "avg_volume_buy_sol" => average(|s| s.avg_volume_buy_sol),
+            "avg_volume_sell_sol" => average(|s| s.avg_volume_sell_sol),
+            "full_range_len" => count(|s| s.full_range_len),
+            "seconds_with_trades" => count(|s| s.seconds_with_trades),
+            "coverage_ratio" => stat(|s| s.coverage_ratio),
+        )
+    }
+
+    // Tri décroissant par défaut, les échecs en dernier
+    pub fn sorted_frame(&self, column: &str, descending: bool) -> error::Result<DataFrame> {
+        let frame = self.frame()?;
+        if !frame.get_column_names().contains(&column) {
+            return Err(error::Error::InvalidArgument(format!(
+                "unknown sort column `{column}` (expected one of: {})",
+                frame.get_column_names().join(", ")
+            )));
+        }
+        let options = SortMultipleOptions::default()
+            .with_order_descending(descending)
+            .with_nulls_last(true)
+            .with_maintain_order(true);
+        Ok(frame.sort([column], options)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::sample::SampleLaunch;
+
+    fn ok(mint: &str, trades: usize) -> BatchEntry {
+        let trades = SampleLaunch::new().trade_envelopes()[..trades].to_vec();
+        BatchEntry {
+            mint: mint.to_string(),
+            outcome: Ok(TokenSummary {
+                name: Some("Sample".to_string()),
+                symbol: Some("SMPL".to_string()),
+                complete: true,
+                stats: TokenStats::new(&trades),
+            }),
+        }
+    }
+
+    fn failed(mint: &str) -> BatchEntry {
+        BatchEntry {
+            mint: mint.to_string(),
+            outcome: Err("token not found".to_string()),
+        }
+    }
+
+    fn report() -> BatchReport {
+        BatchReport {
+            entries: vec![ok("two", 2), failed("lost"), ok("five", 5), ok("three", 3)],
+        }
+    }
+
+    fn mints(frame: &DataFrame) -> Vec<String> {
+        frame
+            .column("mint")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn failed_mints_have_null_stats() {
+        let frame = report().frame().unwrap();
+
+        assert_eq!(frame.height(), 4);
+        let trades = frame.column("total_trades").unwrap();
+        assert_eq!(trades.null_count(), 1);
+        assert_eq!(trades.get(1).unwrap(), AnyValue::Null);
+        assert_eq!(frame.column("error").unwrap().get(1).unwrap(), AnyValue::String("token not found"));
+    }
+
+    #[test]
+    fn sorts_descending_with_failures_last() {
+        let frame = report().sorted_frame("total_trades", true).unwrap();
+
+        assert_eq!(mints(&frame), vec!["five", "three", "two", "lost"]);
+    }
+
+    #[test]
+    fn sorts_ascending_with_failures_still_last() {
+        let frame = report().sorted_frame("total_trades", false).unwrap();
+
+        assert_eq!(mints(&frame), vec!["two", "three", "five", "lost"]);
+    }
+
+    #[test]
+    fn rejects_unknown_sort_columns() {
+        let error = report().sorted_frame("volume", true).unwrap_err();
+
+        let error::Error::InvalidArgument(message) = error else {
+            panic!("unexpected error: {error}");
+        };
+        assert!(message.starts_with("unknown sort column `volume`"));
+        assert!(message.contains("total_volume_sol"));
+    }
+}
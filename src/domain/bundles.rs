@@ -0,0 +1,231 @@
+use crate::domain::base58;
+use crate::platforms::pumpfun::events::TradeEnvelope;
+use serde::{Serialize, Serializer};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// Comptes de tip Jito du mainnet
+pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+
+// Un bundle Jito contient au plus 5 transactions, exécutées à la suite dans le bloc
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+// Ce que l'on sait des transactions d'achat en plus des événements décodés
+#[derive(Debug, Clone, Default)]
+pub struct BundleEvidence {
+    // Lamports versés à un compte de tip Jito, par signature
+    pub tips: HashMap<String, u64>,
+    // Premier wallet ayant financé chaque acheteur
+    pub funders: HashMap<Pubkey, Pubkey>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Bundle {
+    pub slot: u64,
+    // Positions extrêmes dans le bloc, si le bloc a pu être lu
+    pub positions: Option<(usize, usize)>,
+    pub signatures: Vec<String>,
+    #[serde(serialize_with = "base58::pubkeys")]
+    pub wallets: Vec<Pubkey>,
+    pub tip_lamports: u64,
+    pub tokens_bought: u64,
+    pub sol_spent: u64,
+    // Source de financement partagée par au moins deux wallets du bundle
+    #[serde(serialize_with = "base58::option_pubkey")]
+    pub common_funder: Option<Pubkey>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BundleReport {
+    pub bundles: Vec<Bundle>,
+    pub bundled_supply_share: f64,
+    // Part de l'offre encore détenue par les wallets bundlés d'après les trades analysés
+    pub held_supply_share: f64,
+    // Sources de financement communes à plusieurs acheteurs : (funder, wallets financés)
+    #[serde(serialize_with = "funders")]
+    pub funders: Vec<(Pubkey, Vec<Pubkey>)>,
+}
+
+// En JSON : un objet funder → wallets financés, dans l'ordre du rapport
+fn funders<S: Serializer>(funders: &[(Pubkey, Vec<Pubkey>)], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(
+        funders
+            .iter()
+            .map(|(funder, wallets)| (funder.to_string(), wallets.iter().map(Pubkey::to_string).collect::<Vec<_>>())),
+    )
+}
+
+struct BuyTx<'a> {
+    signature: &'a str,
+    position: Option<usize>,
+    trades: Vec<&'a TradeEnvelope>,
+}
+
+// Regroupe les achats par slot puis par suite contiguë de positions dans le bloc.
+// Un groupe est un bundle s'il implique plusieurs wallets et porte un tip Jito ou une source commune.
+pub fn detect_bundles(trades: &[TradeEnvelope], evidence: &BundleEvidence, total_supply: u64) -> BundleReport {
+    let mut by_slot: BTreeMap<u64, Vec<BuyTx>> = BTreeMap::new();
+    for trade in trades.iter().filter(|t| t.is_buy) {
+        let txs = by_slot.entry(trade.slot).or_default();
+        match txs.iter_mut().find(|tx| tx.signature == trade.signature) {
+            Some(tx) => tx.trades.push(trade),
+            None => txs.push(BuyTx {
+                signature: &trade.signature,
+                position: trade.tx_index.map(|i| i as usize),
+                trades: vec![trade],
+            }),
+        }
+    }
+
+    let mut report = BundleReport::default();
+    for (slot, mut txs) in by_slot {
+        txs.sort_by_key(|tx| tx.position.unwrap_or(usize::MAX));
+        for run in split_runs(txs) {
+            if let Some(bundle) = to_bundle(slot, &run, evidence) {
+                report.bundles.push(bundle);
+            }
+        }
+    }
+
+    let bundled: HashSet<Pubkey> = report.bundles.iter().flat_map(|b| b.wallets.iter().copied()).collect();
+    let bought: u64 = report.bundles.iter().map(|b| b.tokens_bought).sum();
+    let mut net: HashMap<Pubkey, i128> = HashMap::new();
+    for trade in trades.iter().filter(|t| bundled.contains(&t.user)) {
+        let delta = trade.token_amount as i128;
+        *net.entry(trade.user).or_default() += if trade.is_buy { delta } else { -delta };
+    }
+    let held: i128 = net.values().map(|v| (*v).max(0)).sum();
+    if total_supply > 0 {
+        report.bundled_supply_share = bought as f64 / total_supply as f64;
+        report.held_supply_share = held as f64 / total_supply as f64;
+    }
+
+    let mut funded: HashMap<Pubkey, Vec<Pubkey>> = HashMap::new();
+    let buyers: HashSet<Pubkey> = trades.iter().filter(|t| t.is_buy).map(|t| t.user).collect();
+    for wallet in buyers {
+        if let Some(funder) = evidence.funders.get(&wallet) {
+            funded.entry(*funder).or_default().push(wallet);
+        }
+    }
+    report.funders = funded.into_iter().filter(|(_, wallets)| wallets.len() > 1).collect();
+    report.funders.sort_by_key(|(_, wallets)| std::cmp::Reverse(wallets.len()));
+
+    if !report.bundles.is_empty() {
+        tracing::info!(
+            bundles = report.bundles.len(),
+            supply_share = report.bundled_supply_share,
+            "📦 bundles détectés"
+        );
+    }
+    report
+}
+
+// Une suite ne tient que des positions adjacentes, et au plus autant de transactions qu'un bundle.
+// Sans position connue, le slot est découpé dans l'ordre des trades.
+fn split_runs(txs: Vec<BuyTx>) -> Vec<Vec<BuyTx>> {
+    let mut runs: Vec<Vec<BuyTx>> = Vec::new();
+    for tx in txs {
+        let contiguous = match (runs.last(), tx.position) {
+            (Some(run), _) if run.len() >= MAX_BUNDLE_TRANSACTIONS => false,
+            (Some(run), Some(position)) => run.last().and_then(|prev| prev.position).is_some_and(|p| position == p + 1),
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        match runs.last_mut() {
+            Some(run) if contiguous => run.push(tx),
+            _ => runs.push(vec![tx]),
+        }
+    }
+    runs
+}
+
+fn to_bundle(slot: u64, run: &[BuyTx], evidence: &BundleEvidence) -> Option<Bundle> {
+    let mut wallets: Vec<Pubkey> = Vec::new();
+    for trade in run.iter().flat_map(|tx| tx.trades.iter()) {
+        if !wallets.contains(&trade.user) {
+            wallets.push(trade.user);
+        }
+    }
+    if wallets.len() < 2 {
+        return None;
+    }
+
+    let tip_lamports: u64 = run.iter().filter_map(|tx| evidence.tips.get(tx.signature)).sum();
+    let mut funder_counts: HashMap<Pubkey, usize> = HashMap::new();
+    for wallet in &wallets {
+        if let Some(funder) = evidence.funders.get(wallet) {
+            *funder_counts.entry(*funder).or_default() += 1;
+        }
+    }
+    let common_funder = funder_counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .max_by_key(|(_, count)| *count)
+        .map(|(funder, _)| funder);
+    if tip_lamports == 0 && common_funder.is_none() {
+        return None;
+    }
+
+    let positions: Vec<usize> = run.iter().filter_map(|tx| tx.position).collect();
+    let trades = run.iter().flat_map(|tx| tx.trades.iter());
+    Some(Bundle {
+        slot,
+        positions: positions.first().zip(positions.last()).map(|(a, b)| (*a, *b)),
+        signatures: run.iter().map(|tx| tx.signature.to_string()).collect(),
+        wallets,
+        tip_lamports,
+        tokens_bought: trades.clone().map(|t| t.token_amount).sum(),
+        sol_spent: trades.map(|t| t.sol_amount + t.fee + t.creator_fee).sum(),
+        common_funder,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(position: Option<usize>) -> BuyTx<'static> {
+        BuyTx {
+            signature: "",
+            position,
+            trades: Vec::new(),
+        }
+    }
+
+    fn positions(runs: &[Vec<BuyTx>]) -> Vec<Vec<Option<usize>>> {
+        runs.iter().map(|run| run.iter().map(|tx| tx.position).collect()).collect()
+    }
+
+    #[test]
+    fn runs_break_on_any_gap_in_the_block() {
+        let runs = split_runs([3, 4, 6, 7, 8].map(|p| tx(Some(p))).into());
+
+        assert_eq!(positions(&runs), vec![vec![Some(3), Some(4)], vec![Some(6), Some(7), Some(8)]]);
+    }
+
+    #[test]
+    fn runs_never_exceed_a_bundle() {
+        let runs = split_runs((10..22).map(|p| tx(Some(p))).collect());
+
+        let lengths: Vec<usize> = runs.iter().map(Vec::len).collect();
+        assert_eq!(lengths, vec![MAX_BUNDLE_TRANSACTIONS, MAX_BUNDLE_TRANSACTIONS, 2]);
+        assert_eq!(runs[1][0].position, Some(15));
+    }
+
+    #[test]
+    fn unknown_positions_are_capped_too() {
+        let runs = split_runs((0..7).map(|_| tx(None)).collect());
+
+        let lengths: Vec<usize> = runs.iter().map(Vec::len).collect();
+        assert_eq!(lengths, vec![MAX_BUNDLE_TRANSACTIONS, 2]);
+    }
+}
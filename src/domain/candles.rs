@@ -0,0 +1,204 @@
+use crate::domain::units::{LAMPORTS_PER_SOL, TOKEN_UNIT};
+use crate::platforms::pumpfun::curve::CurveState;
+use crate::platforms::pumpfun::events::{TradeEnvelope, TradeEvent};
+use polars::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Seconds1,
+    Seconds15,
+    Minutes1,
+    Minutes5,
+    Hours1,
+}
+
+impl Resolution {
+    pub fn seconds(&self) -> u64 {
+        match self {
+            Resolution::Seconds1 => 1,
+            Resolution::Seconds15 => 15,
+            Resolution::Minutes1 => 60,
+            Resolution::Minutes5 => 300,
+            Resolution::Hours1 => 3_600,
+        }
+    }
+}
+
+impl Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Resolution::Seconds1 => "1s",
+            Resolution::Seconds15 => "15s",
+            Resolution::Minutes1 => "1m",
+            Resolution::Minutes5 => "5m",
+            Resolution::Hours1 => "1h",
+        };
+        write!(f, "{s}")
+    }
+}
+
+// Prix en SOL par token entier, volumes en SOL et en tokens entiers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candle {
+    // Début de la bougie, en secondes unix
+    pub start: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub buy_volume_sol: f64,
+    pub sell_volume_sol: f64,
+    pub buy_volume_tokens: f64,
+    pub sell_volume_tokens: f64,
+    pub trades: u32,
+}
+
+impl Candle {
+    fn open_at(start: u64, price: f64) -> Self {
+        Self {
+            start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            buy_volume_sol: 0.0,
+            sell_volume_sol: 0.0,
+            buy_volume_tokens: 0.0,
+            sell_volume_tokens: 0.0,
+            trades: 0,
+        }
+    }
+
+    fn push(&mut self, trade: &TradeEvent, price: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        let sol = trade.sol_amount as f64 / LAMPORTS_PER_SOL;
+        let tokens = trade.token_amount as f64 / TOKEN_UNIT;
+        if trade.is_buy {
+            self.buy_volume_sol += sol;
+            self.buy_volume_tokens += tokens;
+        } else {
+            self.sell_volume_sol += sol;
+            self.sell_volume_tokens += tokens;
+        }
+        self.trades += 1;
+    }
+}
+
+// Les trades doivent être en ordre chronologique ; les périodes sans trade ne produisent pas de bougie
+pub fn build_candles(trades: &[TradeEnvelope], resolution: Resolution) -> Vec<Candle> {
+    let step = resolution.seconds();
+    let mut candles: BTreeMap<u64, Candle> = BTreeMap::new();
+    for trade in trades {
+        // Prix après le trade, déduit des réserves virtuelles publiées dans l'événement
+        let price = CurveState::from(&trade.event).spot_price();
+        let start = trade.timestamp - trade.timestamp % step;
+        candles
+            .entry(start)
+            .or_insert_with(|| Candle::open_at(start, price))
+            .push(trade, price);
+    }
+    candles.into_values().collect()
+}
+
+pub fn candles_frame(candles: &[Candle]) -> PolarsResult<DataFrame> {
+    df!(
+        "timestamp" => candles.iter().map(|c| c.start).collect::<Vec<_>>(),
+        "open" => candles.iter().map(|c| c.open).collect::<Vec<_>>(),
+        "high" => candles.iter().map(|c| c.high).collect::<Vec<_>>(),
+        "low" => candles.iter().map(|c| c.low).collect::<Vec<_>>(),
+        "close" => candles.iter().map(|c| c.close).collect::<Vec<_>>(),
+        "buy_volume_sol" => candles.iter().map(|c| c.buy_volume_sol).collect::<Vec<_>>(),
+        "sell_volume_sol" => candles.iter().map(|c| c.sell_volume_sol).collect::<Vec<_>>(),
+        "buy_volume_tokens" => candles.iter().map(|c| c.buy_volume_tokens).collect::<Vec<_>>(),
+        "sell_volume_tokens" => candles.iter().map(|c| c.sell_volume_tokens).collect::<Vec<_>>(),
+        "trades" => candles.iter().map(|c| c.trades).collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::sample::SampleLaunch;
+
+    // Début d'heure : aligné sur toutes les résolutions
+    const HOUR: u64 = 1_749_999_600;
+
+    // Trade à `HOUR + offset` ; les réserves placent le prix après trade à `price` lamports par token entier
+    fn trade(offset: u64, is_buy: bool, sol_amount: u64, token_amount: u64, price: u64) -> TradeEnvelope {
+        let mut trade = SampleLaunch::new().trade_envelopes().remove(0);
+        trade.event.timestamp = HOUR + offset;
+        trade.event.is_buy = is_buy;
+        trade.event.sol_amount = sol_amount;
+        trade.event.token_amount = token_amount;
+        trade.event.virtual_sol_reserves = price;
+        trade.event.virtual_token_reserves = TOKEN_UNIT as u64;
+        trade
+    }
+
+    fn sol(lamports: u64) -> f64 {
+        lamports as f64 / LAMPORTS_PER_SOL
+    }
+
+    fn trades() -> Vec<TradeEnvelope> {
+        vec![
+            trade(5, true, 1_000_000_000, 100_000_000, 1_000),
+            trade(10, false, 500_000_000, 40_000_000, 3_000),
+            trade(14, true, 250_000_000, 30_000_000, 500),
+            trade(20, false, 100_000_000, 10_000_000, 2_000),
+            trade(400, true, 2_000_000_000, 150_000_000, 4_000),
+        ]
+    }
+
+    #[test]
+    fn buckets_align_on_resolution_boundaries() {
+        let starts = |resolution| build_candles(&trades(), resolution).iter().map(|c| c.start - HOUR).collect::<Vec<_>>();
+
+        assert_eq!(starts(Resolution::Seconds1), vec![5, 10, 14, 20, 400]);
+        // Pas de bougie pour les périodes sans trade
+        assert_eq!(starts(Resolution::Seconds15), vec![0, 15, 390]);
+        assert_eq!(starts(Resolution::Minutes1), vec![0, 360]);
+        assert_eq!(starts(Resolution::Minutes5), vec![0, 300]);
+        assert_eq!(starts(Resolution::Hours1), vec![0]);
+    }
+
+    #[test]
+    fn open_high_low_close_follow_trade_order() {
+        let candles = build_candles(&trades(), Resolution::Seconds15);
+
+        let first = &candles[0];
+        assert_eq!(first.trades, 3);
+        assert_eq!((first.open, first.high, first.low, first.close), (sol(1_000), sol(3_000), sol(500), sol(500)));
+        let hour = &build_candles(&trades(), Resolution::Hours1)[0];
+        assert_eq!((hour.open, hour.high, hour.low, hour.close), (sol(1_000), sol(4_000), sol(500), sol(4_000)));
+    }
+
+    #[test]
+    fn volumes_are_split_between_buys_and_sells() {
+        let candles = build_candles(&trades(), Resolution::Seconds15);
+
+        let first = &candles[0];
+        assert_eq!(first.buy_volume_sol, sol(1_250_000_000));
+        assert_eq!(first.sell_volume_sol, sol(500_000_000));
+        assert_eq!((first.buy_volume_tokens, first.sell_volume_tokens), (130.0, 40.0));
+        let second = &candles[1];
+        assert_eq!((second.buy_volume_sol, second.sell_volume_sol), (0.0, sol(100_000_000)));
+        assert_eq!(second.trades, 1);
+    }
+
+    #[test]
+    fn coarser_resolutions_keep_the_totals() {
+        for resolution in [Resolution::Seconds1, Resolution::Minutes1, Resolution::Hours1] {
+            let candles = build_candles(&trades(), resolution);
+            let buys: f64 = candles.iter().map(|c| c.buy_volume_sol).sum();
+            let sells: f64 = candles.iter().map(|c| c.sell_volume_tokens).sum();
+
+            assert!((buys - 3.25).abs() < 1e-12, "{resolution}");
+            assert_eq!(sells, 50.0, "{resolution}");
+            assert_eq!(candles.iter().map(|c| c.trades).sum::<u32>(), 5, "{resolution}");
+        }
+    }
+}
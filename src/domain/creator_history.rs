@@ -0,0 +1,114 @@
+use crate::domain::base58;
+use crate::platforms::pumpfun::accounts::BondingCurve;
+use crate::platforms::pumpfun::curve::CurveState;
+use crate::platforms::pumpfun::events::{CreateEnvelope, TradeEnvelope};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+// Une sortie du créateur moins d'une heure après le lancement compte comme rapide
+const QUICK_EXIT_SECONDS: u64 = 3_600;
+// Nombre de lancements à partir duquel le volume seul ne fait plus monter le score
+const SERIAL_LAUNCHES: usize = 10;
+
+// Issue d'un token lancé par le créateur
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchOutcome {
+    #[serde(serialize_with = "base58::pubkey")]
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub signature: String,
+    pub slot: u64,
+    pub launched_at: u64,
+    // None si la courbe n'a pas pu être lue
+    pub graduated: Option<bool>,
+    // Plus haute capitalisation observée dans les trades parcourus
+    pub peak_market_cap_sol: Option<f64>,
+    pub creator_last_sell: Option<u64>,
+    pub trades_seen: usize,
+    // false si seule une partie de l'historique du mint a été parcourue
+    pub trades_complete: bool,
+}
+
+impl LaunchOutcome {
+    pub fn new(
+        create: &CreateEnvelope,
+        curve: Option<&BondingCurve>,
+        trades: &[TradeEnvelope],
+        trades_complete: bool,
+    ) -> Self {
+        let own: Vec<&TradeEnvelope> = trades.iter().filter(|t| t.mint == create.mint).collect();
+        let initial = CurveState::from(&create.event).market_cap_sol();
+        let peak = own
+            .iter()
+            .map(|t| CurveState::from(&t.event).market_cap_sol())
+            .fold(initial, f64::max);
+        Self {
+            mint: create.mint,
+            name: create.name.clone(),
+            symbol: create.symbol.clone(),
+            signature: create.signature.clone(),
+            slot: create.slot,
+            launched_at: create.timestamp,
+            graduated: curve.map(|c| c.complete),
+            peak_market_cap_sol: (!own.is_empty()).then_some(peak),
+            creator_last_sell: own
+                .iter()
+                .filter(|t| !t.is_buy && t.user == create.creator)
+                .map(|t| t.timestamp)
+                .max(),
+            trades_seen: own.len(),
+            trades_complete,
+        }
+    }
+
+    pub fn time_to_last_sell(&self) -> Option<u64> {
+        self.creator_last_sell.map(|t| t.saturating_sub(self.launched_at))
+    }
+
+    // Le créateur a vendu un token qui n'a jamais gradué
+    pub fn is_rug(&self) -> bool {
+        self.graduated == Some(false) && self.creator_last_sell.is_some()
+    }
+
+    pub fn is_quick_exit(&self) -> bool {
+        self.time_to_last_sell().is_some_and(|t| t <= QUICK_EXIT_SECONDS)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatorHistory {
+    #[serde(serialize_with = "base58::pubkey")]
+    pub creator: Pubkey,
+    // Mint à partir duquel le créateur a été trouvé, exclu du score
+    #[serde(serialize_with = "base58::option_pubkey")]
+    pub current: Option<Pubkey>,
+    // Du plus récent au plus ancien
+    pub launches: Vec<LaunchOutcome>,
+    pub signatures_scanned: usize,
+    // false si l'historique du créateur a été tronqué
+    pub history_complete: bool,
+}
+
+impl CreatorHistory {
+    pub fn previous(&self) -> impl Iterator<Item = &LaunchOutcome> {
+        self.launches.iter().filter(|l| Some(l.mint) != self.current)
+    }
+
+    pub fn graduated(&self) -> usize {
+        self.previous().filter(|l| l.graduated == Some(true)).count()
+    }
+
+    // Score de 0 à 100 : 60 points pour la part de rugs, 20 pour la part de sorties rapides,
+    // 20 pour le nombre de lancements (plafonné à SERIAL_LAUNCHES)
+    pub fn rugger_score(&self) -> f64 {
+        let launches = self.previous().count();
+        if launches == 0 {
+            return 0.0;
+        }
+        let ratio = |count: usize| count as f64 / launches as f64;
+        let rugs = self.previous().filter(|l| l.is_rug()).count();
+        let quick = self.previous().filter(|l| l.is_quick_exit()).count();
+        60.0 * ratio(rugs) + 20.0 * ratio(quick) + 20.0 * launches.min(SERIAL_LAUNCHES) as f64 / SERIAL_LAUNCHES as f64
+    }
+}
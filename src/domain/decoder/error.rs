@@ -1,3 +1,5 @@
+use solana_sdk::program_error::ProgramError;
+
 #[derive(Debug, thiserror::Error)]
 pub enum DecodeError {
     #[error("short buffer while reading {0}")]
@@ -9,6 +11,10 @@ pub enum DecodeError {
         context: &'static str,
         tag: u32,
     },
+    #[error("invalid mint account: {0}")]
+    Mint(ProgramError),
+    #[error("invalid token account: {0}")]
+    TokenAccount(ProgramError),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
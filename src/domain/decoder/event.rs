@@ -1,5 +1,10 @@
 use super::error::Result;
+use super::helpers::{extract_logs, extract_signature, program_data};
 use crate::platforms::platforms::Platform;
+use serde::Serialize;
+use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+use std::collections::HashSet;
+use std::ops::Deref;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EventKind {
@@ -14,5 +19,127 @@ pub trait EventDecoder {
     fn platform(&self) -> Platform;
     fn classify(&self, payload: &[u8]) -> Option<EventKind>;
     fn decode_create(&self, payload: &[u8]) -> Result<Self::Create>;
-    fn decode_trade(&self, signature: &String, payload: &[u8]) -> Result<Self::Trade>;
+    fn decode_trade(&self, payload: &[u8]) -> Result<Self::Trade>;
+}
+
+#[derive(Debug)]
+pub enum DecodedEvent<C, T> {
+    Create(C),
+    Trade(T),
+}
+
+// Un événement décodé et sa place dans la chaîne : (slot, tx_index, event_index) l'ordonne,
+// (signature, event_index) l'identifie
+#[derive(Debug, Clone, Serialize)]
+pub struct EventEnvelope<E> {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    // Position de la transaction dans son bloc, connue seulement après lecture du bloc
+    pub tx_index: Option<u32>,
+    // Rang du blob « Program data: » dans les logs de la transaction
+    pub event_index: u32,
+    // Aplati : une ligne JSON par événement, métadonnées et champs décodés côte à côte
+    #[serde(flatten)]
+    pub event: E,
+}
+
+impl<E> EventEnvelope<E> {
+    pub fn key(&self) -> (&str, u32) {
+        (&self.signature, self.event_index)
+    }
+}
+
+impl<E> Deref for EventEnvelope<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.event
+    }
+}
+
+impl<C, T> EventEnvelope<DecodedEvent<C, T>> {
+    pub fn split(self) -> DecodedEvent<EventEnvelope<C>, EventEnvelope<T>> {
+        let EventEnvelope { signature, slot, block_time, tx_index, event_index, event } = self;
+        match event {
+            DecodedEvent::Create(event) => DecodedEvent::Create(EventEnvelope {
+                signature,
+                slot,
+                block_time,
+                tx_index,
+                event_index,
+                event,
+            }),
+            DecodedEvent::Trade(event) => DecodedEvent::Trade(EventEnvelope {
+                signature,
+                slot,
+                block_time,
+                tx_index,
+                event_index,
+                event,
+            }),
+        }
+    }
+}
+
+// Événement décodé par `D`, dans son enveloppe
+pub type DecodedEnvelope<D> = EventEnvelope<DecodedEvent<<D as EventDecoder>::Create, <D as EventDecoder>::Trade>>;
+
+// Décode les blobs « Program data: » d'une transaction reconnus par la plateforme
+pub fn decode_transaction<D: EventDecoder>(
+    decoder: &D,
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<Vec<DecodedEnvelope<D>>> {
+    let Some(blobs) = extract_logs(tx) else {
+        return Ok(Vec::new());
+    };
+    let signature = extract_signature(tx).unwrap_or_default();
+    decode_blobs(decoder, signature, tx.slot, tx.block_time, &blobs)
+}
+
+// Même décodage pour les logs reçus en direct, sans heure de bloc
+pub fn decode_logs<D: EventDecoder>(
+    decoder: &D,
+    signature: String,
+    slot: u64,
+    logs: &[String],
+) -> Result<Vec<DecodedEnvelope<D>>> {
+    decode_blobs(decoder, signature, slot, None, &program_data(logs))
+}
+
+fn decode_blobs<D: EventDecoder>(
+    decoder: &D,
+    signature: String,
+    slot: u64,
+    block_time: Option<i64>,
+    blobs: &[Vec<u8>],
+) -> Result<Vec<DecodedEnvelope<D>>> {
+    let mut events = Vec::new();
+    for (index, blob) in blobs.iter().enumerate() {
+        let event = match decoder.classify(blob) {
+            Some(EventKind::Create) => DecodedEvent::Create(decoder.decode_create(blob)?),
+            Some(EventKind::Trade) => DecodedEvent::Trade(decoder.decode_trade(blob)?),
+            None => continue,
+        };
+        events.push(EventEnvelope {
+            signature: signature.clone(),
+            slot,
+            block_time,
+            tx_index: None,
+            event_index: index as u32,
+            event,
+        });
+    }
+    Ok(events)
+}
+
+// Tri stable par (slot, tx_index) : sans position connue, l'ordre de récupération est conservé dans le slot,
+// et les événements d'une même transaction restent dans l'ordre des logs. Les doublons sont retirés.
+pub fn order_events<E>(events: &mut Vec<EventEnvelope<E>>) {
+    events.sort_by_key(|e| (e.slot, e.tx_index));
+    let mut seen = HashSet::new();
+    events.retain(|e| {
+        let (signature, event_index) = e.key();
+        seen.insert((signature.to_string(), event_index))
+    });
 }
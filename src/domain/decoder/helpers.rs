@@ -1,6 +1,6 @@
 use super::error::{self, DecodeError};
 use solana_sdk::pubkey::Pubkey;
-use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+use solana_transaction_status_client_types::{EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction};
 use solana_transaction_status_client_types::option_serializer::OptionSerializer;
 use base64::decode;
 use spl_token::solana_program::program_option::COption;
@@ -10,14 +10,7 @@ pub fn extract_logs(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Option<Ve
     match tx.transaction.meta.as_ref() {
         Some(meta) => {
             if let OptionSerializer::Some(log) = meta.log_messages.as_ref() {
-                let interesting_log = log
-                    .iter()
-                    .filter_map(|log| {
-                        log.strip_prefix("Program data: ")
-                            .and_then(|data| decode(data).ok())
-                    })
-                    .collect::<Vec<Vec<u8>>>();
-                return Some(interesting_log);
+                return Some(program_data(log));
             } else {
                 None
             }
@@ -27,6 +20,24 @@ pub fn extract_logs(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Option<Ve
 
 }
 
+// Blobs « Program data: » décodés, qu'ils viennent d'une transaction ou d'une notification logsSubscribe
+pub fn program_data(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|log| {
+            log.strip_prefix("Program data: ")
+                .and_then(|data| decode(data).ok())
+        })
+        .collect()
+}
+
+// Première signature de la transaction, celle qui l'identifie
+pub fn extract_signature(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Option<String> {
+    match &tx.transaction.transaction {
+        EncodedTransaction::Json(inner) => inner.signatures.first().cloned(),
+        _ => None,
+    }
+}
+
 pub fn read_u16_le(input: &mut &[u8]) -> error::Result<u16> {
     if input.len() < 2 {
         return Err(DecodeError::ShortBuffer("u16"));
@@ -0,0 +1,448 @@
+use super::error::{DecodeError, Result};
+use crate::domain::base58;
+use serde::Serialize;
+use solana_sdk::account::Account;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use spl_token_2022::extension::non_transferable::NonTransferable;
+use spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use spl_token_2022::extension::transfer_hook::TransferHook;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenProgram {
+    SplToken,
+    Token2022,
+}
+
+impl TokenProgram {
+    pub fn from_owner(owner: &Pubkey) -> Option<Self> {
+        match *owner {
+            spl_token::ID => Some(TokenProgram::SplToken),
+            spl_token_2022::ID => Some(TokenProgram::Token2022),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TokenProgram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenProgram::SplToken => write!(f, "SPL Token"),
+            TokenProgram::Token2022 => write!(f, "Token-2022"),
+        }
+    }
+}
+
+// Extensions Token-2022 qui changent le comportement du token pour un acheteur
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MintExtension {
+    // Frais en vigueur à l'époque courante ; `scheduled` annonce un changement déjà programmé
+    TransferFee { basis_points: u16, maximum_fee: u64, scheduled: Option<ScheduledTransferFee> },
+    PermanentDelegate(#[serde(serialize_with = "base58::option_pubkey")] Option<Pubkey>),
+    NonTransferable,
+    TransferHook(#[serde(serialize_with = "base58::option_pubkey")] Option<Pubkey>),
+    MetadataPointer {
+        #[serde(serialize_with = "base58::option_pubkey")]
+        authority: Option<Pubkey>,
+        #[serde(serialize_with = "base58::option_pubkey")]
+        address: Option<Pubkey>,
+    },
+    // Extensions présentes mais sans incidence sur le risque
+    Other(#[serde(serialize_with = "base58::debug")] ExtensionType),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ScheduledTransferFee {
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+    pub epoch: u64,
+}
+
+impl Display for MintExtension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn or_none(key: &Option<Pubkey>) -> String {
+            key.map(|k| k.to_string()).unwrap_or_else(|| "none".to_string())
+        }
+        match self {
+            MintExtension::TransferFee { basis_points, maximum_fee, scheduled } => {
+                write!(
+                    f,
+                    "transfer fee of {:.2}% (max {maximum_fee} base units)",
+                    *basis_points as f64 / 100.0
+                )?;
+                match scheduled {
+                    Some(next) => write!(
+                        f,
+                        ", changing to {:.2}% (max {} base units) at epoch {}",
+                        next.basis_points as f64 / 100.0,
+                        next.maximum_fee,
+                        next.epoch
+                    ),
+                    None => Ok(()),
+                }
+            }
+            MintExtension::PermanentDelegate(delegate) => {
+                write!(f, "permanent delegate {} can move or burn any holder's tokens", or_none(delegate))
+            }
+            MintExtension::NonTransferable => write!(f, "non-transferable: tokens cannot be sold"),
+            MintExtension::TransferHook(program) => {
+                write!(f, "transfer hook program {} runs on every transfer", or_none(program))
+            }
+            MintExtension::MetadataPointer { authority, address } => write!(
+                f,
+                "metadata pointer to {} can be redirected by {}",
+                or_none(address),
+                or_none(authority)
+            ),
+            MintExtension::Other(kind) => write!(f, "{kind:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MintInfo {
+    pub program: TokenProgram,
+    pub decimals: u8,
+    pub supply: u64,
+    #[serde(serialize_with = "base58::option_pubkey")]
+    pub mint_authority: Option<Pubkey>,
+    #[serde(serialize_with = "base58::option_pubkey")]
+    pub freeze_authority: Option<Pubkey>,
+    pub extensions: Vec<MintExtension>,
+}
+
+impl MintInfo {
+    // Extensions à signaler dans le rapport ; une extension inerte (délégué absent, frais à 0) ne compte pas
+    pub fn risk_flags(&self) -> Vec<&MintExtension> {
+        self.extensions
+            .iter()
+            .filter(|ext| match ext {
+                MintExtension::TransferFee { basis_points, scheduled, .. } => {
+                    *basis_points > 0 || scheduled.is_some_and(|next| next.basis_points > 0)
+                }
+                MintExtension::PermanentDelegate(delegate) => delegate.is_some(),
+                MintExtension::NonTransferable => true,
+                MintExtension::TransferHook(program) => program.is_some(),
+                // Un pointeur figé vers les métadonnées du mint est l'usage normal
+                MintExtension::MetadataPointer { authority, .. } => authority.is_some(),
+                MintExtension::Other(_) => false,
+            })
+            .collect()
+    }
+}
+
+// `None` si le compte n'appartient à aucun des deux programmes token.
+// `epoch` choisit les frais de transfert en vigueur parmi les deux configurations du mint
+pub fn decode_mint(account: &Account, epoch: u64) -> Option<Result<MintInfo>> {
+    let program = TokenProgram::from_owner(&account.owner)?;
+    Some(match program {
+        TokenProgram::SplToken => decode_spl_mint(&account.data),
+        TokenProgram::Token2022 => decode_token_2022_mint(&account.data, epoch),
+    })
+}
+
+// Compte de token d'un holder ; même disposition de base pour les deux programmes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenAccountInfo {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+pub fn decode_token_account(account: &Account) -> Option<Result<TokenAccountInfo>> {
+    TokenProgram::from_owner(&account.owner)?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data);
+    Some(
+        state
+            .map(|state| TokenAccountInfo {
+                mint: state.base.mint,
+                owner: state.base.owner,
+                amount: state.base.amount,
+            })
+            .map_err(DecodeError::TokenAccount),
+    )
+}
+
+fn decode_spl_mint(data: &[u8]) -> Result<MintInfo> {
+    let mint = spl_token::state::Mint::unpack(data).map_err(DecodeError::Mint)?;
+    Ok(MintInfo {
+        program: TokenProgram::SplToken,
+        decimals: mint.decimals,
+        supply: mint.supply,
+        mint_authority: mint.mint_authority.into(),
+        freeze_authority: mint.freeze_authority.into(),
+        extensions: Vec::new(),
+    })
+}
+
+fn decode_token_2022_mint(data: &[u8], epoch: u64) -> Result<MintInfo> {
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data).map_err(DecodeError::Mint)?;
+
+    let mut extensions = Vec::new();
+    for kind in state.get_extension_types().map_err(DecodeError::Mint)? {
+        let extension = match kind {
+            ExtensionType::TransferFeeConfig => {
+                let config = state.get_extension::<TransferFeeConfig>().map_err(DecodeError::Mint)?;
+                let current = config.get_epoch_fee(epoch);
+                let newer = &config.newer_transfer_fee;
+                let changes = current.transfer_fee_basis_points != newer.transfer_fee_basis_points
+                    || current.maximum_fee != newer.maximum_fee;
+                let scheduled = changes.then(|| ScheduledTransferFee {
+                    basis_points: u16::from(newer.transfer_fee_basis_points),
+                    maximum_fee: u64::from(newer.maximum_fee),
+                    epoch: u64::from(newer.epoch),
+                });
+                MintExtension::TransferFee {
+                    basis_points: u16::from(current.transfer_fee_basis_points),
+                    maximum_fee: u64::from(current.maximum_fee),
+                    scheduled,
+                }
+            }
+            ExtensionType::PermanentDelegate => {
+                let ext = state.get_extension::<PermanentDelegate>().map_err(DecodeError::Mint)?;
+                MintExtension::PermanentDelegate(ext.delegate.into())
+            }
+            ExtensionType::NonTransferable => {
+                state.get_extension::<NonTransferable>().map_err(DecodeError::Mint)?;
+                MintExtension::NonTransferable
+            }
+            ExtensionType::TransferHook => {
+                let ext = state.get_extension::<TransferHook>().map_err(DecodeError::Mint)?;
+                MintExtension::TransferHook(ext.program_id.into())
+            }
+            ExtensionType::MetadataPointer => {
+                let ext = state.get_extension::<MetadataPointer>().map_err(DecodeError::Mint)?;
+                MintExtension::MetadataPointer {
+                    authority: ext.authority.into(),
+                    address: ext.metadata_address.into(),
+                }
+            }
+            other => MintExtension::Other(other),
+        };
+        extensions.push(extension);
+    }
+
+    Ok(MintInfo {
+        program: TokenProgram::Token2022,
+        decimals: state.base.decimals,
+        supply: state.base.supply,
+        mint_authority: state.base.mint_authority.into(),
+        freeze_authority: state.base.freeze_authority.into(),
+        extensions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::sample::key;
+    use spl_token_2022::extension::transfer_fee::TransferFee;
+    use spl_token_2022::extension::{BaseStateWithExtensionsMut, StateWithExtensionsMut};
+    use spl_token_2022::state::{Account as TokenAccount, AccountState, Mint};
+
+    const SUPPLY: u64 = 1_000_000_000_000_000;
+
+    fn account(owner: Pubkey, data: Vec<u8>) -> Account {
+        Account { lamports: 1, data, owner, executable: false, rent_epoch: 0 }
+    }
+
+    // Mint Token-2022 avec les extensions demandées, initialisées par `init`
+    fn token_2022_mint(kinds: &[ExtensionType], init: impl FnOnce(&mut StateWithExtensionsMut<Mint>)) -> Account {
+        let mut data = vec![0; ExtensionType::try_calculate_account_len::<Mint>(kinds).unwrap()];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        state.base = Mint {
+            mint_authority: Some(key(7)).into(),
+            supply: SUPPLY,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        init(&mut state);
+        account(spl_token_2022::ID, data)
+    }
+
+    fn transfer_fee(epoch: u64, basis_points: u16, maximum_fee: u64) -> TransferFee {
+        TransferFee {
+            epoch: epoch.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: basis_points.into(),
+        }
+    }
+
+    // Frais de 1 % jusqu'à l'époque 10, puis 5 %
+    fn mint_with_fee_change() -> Account {
+        token_2022_mint(&[ExtensionType::TransferFeeConfig], |state| {
+            let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+            config.older_transfer_fee = transfer_fee(0, 100, 1_000);
+            config.newer_transfer_fee = transfer_fee(10, 500, 5_000);
+        })
+    }
+
+    #[test]
+    fn spl_mints_have_no_extensions() {
+        let mint = spl_token::state::Mint {
+            mint_authority: None.into(),
+            supply: SUPPLY,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: Some(key(8)).into(),
+        };
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(mint, &mut data).unwrap();
+
+        let info = decode_mint(&account(spl_token::ID, data), 0).unwrap().unwrap();
+
+        assert_eq!(info.program, TokenProgram::SplToken);
+        assert_eq!((info.supply, info.decimals), (SUPPLY, 6));
+        assert_eq!((info.mint_authority, info.freeze_authority), (None, Some(key(8))));
+        assert!(info.extensions.is_empty());
+        assert!(info.risk_flags().is_empty());
+    }
+
+    #[test]
+    fn accounts_of_other_programs_are_not_mints() {
+        assert!(decode_mint(&account(key(9), vec![0; 82]), 0).is_none());
+        assert!(decode_token_account(&account(key(9), vec![0; 165])).is_none());
+    }
+
+    #[test]
+    fn transfer_fee_is_the_one_in_force_with_the_next_one_scheduled() {
+        let info = decode_mint(&mint_with_fee_change(), 5).unwrap().unwrap();
+
+        assert_eq!(info.program, TokenProgram::Token2022);
+        assert_eq!(
+            info.extensions,
+            vec![MintExtension::TransferFee {
+                basis_points: 100,
+                maximum_fee: 1_000,
+                scheduled: Some(ScheduledTransferFee { basis_points: 500, maximum_fee: 5_000, epoch: 10 }),
+            }]
+        );
+    }
+
+    #[test]
+    fn transfer_fee_switches_once_the_scheduled_epoch_is_reached() {
+        let info = decode_mint(&mint_with_fee_change(), 10).unwrap().unwrap();
+
+        assert_eq!(
+            info.extensions,
+            vec![MintExtension::TransferFee { basis_points: 500, maximum_fee: 5_000, scheduled: None }]
+        );
+    }
+
+    #[test]
+    fn a_scheduled_fee_on_a_free_mint_is_a_risk() {
+        let mint = token_2022_mint(&[ExtensionType::TransferFeeConfig], |state| {
+            let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+            config.older_transfer_fee = transfer_fee(0, 0, 0);
+            config.newer_transfer_fee = transfer_fee(10, 300, 1_000);
+        });
+
+        let scheduled = decode_mint(&mint, 5).unwrap().unwrap();
+        let free = token_2022_mint(&[ExtensionType::TransferFeeConfig], |state| {
+            state.init_extension::<TransferFeeConfig>(true).unwrap();
+        });
+        let free = decode_mint(&free, 5).unwrap().unwrap();
+
+        assert_eq!(scheduled.risk_flags().len(), 1);
+        assert!(free.risk_flags().is_empty());
+    }
+
+    #[test]
+    fn decodes_every_risky_extension() {
+        let kinds = [
+            ExtensionType::PermanentDelegate,
+            ExtensionType::NonTransferable,
+            ExtensionType::TransferHook,
+            ExtensionType::MetadataPointer,
+        ];
+        let mint = token_2022_mint(&kinds, |state| {
+            state.init_extension::<PermanentDelegate>(true).unwrap().delegate = Some(key(1)).try_into().unwrap();
+            state.init_extension::<NonTransferable>(true).unwrap();
+            let hook = state.init_extension::<TransferHook>(true).unwrap();
+            hook.program_id = Some(key(2)).try_into().unwrap();
+            let pointer = state.init_extension::<MetadataPointer>(true).unwrap();
+            pointer.authority = Some(key(3)).try_into().unwrap();
+            pointer.metadata_address = Some(key(4)).try_into().unwrap();
+        });
+
+        let info = decode_mint(&mint, 0).unwrap().unwrap();
+
+        let expected = vec![
+            MintExtension::PermanentDelegate(Some(key(1))),
+            MintExtension::NonTransferable,
+            MintExtension::TransferHook(Some(key(2))),
+            MintExtension::MetadataPointer { authority: Some(key(3)), address: Some(key(4)) },
+        ];
+        assert_eq!(info.extensions, expected);
+        assert_eq!(info.risk_flags().len(), 4);
+    }
+
+    #[test]
+    fn inert_extensions_are_not_risk_flags() {
+        let kinds = [
+            ExtensionType::PermanentDelegate,
+            ExtensionType::TransferHook,
+            ExtensionType::MetadataPointer,
+            ExtensionType::MintCloseAuthority,
+        ];
+        // Délégué et hook absents, pointeur sans autorité : les extensions existent mais ne font rien
+        let mint = token_2022_mint(&kinds, |state| {
+            state.init_extension::<PermanentDelegate>(true).unwrap();
+            state.init_extension::<TransferHook>(true).unwrap();
+            let pointer = state.init_extension::<MetadataPointer>(true).unwrap();
+            pointer.metadata_address = Some(key(4)).try_into().unwrap();
+            state
+                .init_extension::<spl_token_2022::extension::mint_close_authority::MintCloseAuthority>(true)
+                .unwrap();
+        });
+
+        let info = decode_mint(&mint, 0).unwrap().unwrap();
+
+        assert_eq!(info.extensions.len(), 4);
+        assert_eq!(info.extensions[3], MintExtension::Other(ExtensionType::MintCloseAuthority));
+        assert!(info.risk_flags().is_empty());
+    }
+
+    #[test]
+    fn decodes_token_accounts_of_both_programs() {
+        let token = TokenAccount {
+            mint: key(1),
+            owner: key(2),
+            amount: 42,
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        };
+        let mut spl_data = vec![0; TokenAccount::LEN];
+        TokenAccount::pack(token, &mut spl_data).unwrap();
+
+        // Compte Token-2022 plus long que la disposition de base à cause de ses extensions
+        let kinds = [ExtensionType::ImmutableOwner];
+        let mut data = vec![0; ExtensionType::try_calculate_account_len::<TokenAccount>(&kinds).unwrap()];
+        let mut state = StateWithExtensionsMut::<TokenAccount>::unpack_uninitialized(&mut data).unwrap();
+        state.base = token;
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state
+            .init_extension::<spl_token_2022::extension::immutable_owner::ImmutableOwner>(true)
+            .unwrap();
+
+        let expected = TokenAccountInfo { mint: key(1), owner: key(2), amount: 42 };
+        for account in [account(spl_token::ID, spl_data), account(spl_token_2022::ID, data)] {
+            assert_eq!(decode_token_account(&account).unwrap().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn a_truncated_token_account_is_an_error() {
+        assert!(decode_token_account(&account(spl_token_2022::ID, vec![0; 40])).unwrap().is_err());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod account;
 pub mod event;
 pub mod helpers;
+pub mod mint;
 pub mod error;
\ No newline at end of file
@@ -0,0 +1,232 @@
+use crate::domain::analysis::Creation;
+use crate::domain::base58;
+use crate::platforms::pumpfun::events::{CreateEvent, TradeEnvelope};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+// Trade du dev, daté par rapport au lancement
+#[derive(Debug, Clone, Serialize)]
+pub struct DevTrade {
+    pub signature: String,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub token_amount: u64,
+    // SOL payé frais compris (achat) ou reçu net de frais (vente)
+    pub sol_amount: u64,
+    pub seconds_after_launch: Option<u64>,
+    pub slots_after_launch: Option<u64>,
+}
+
+// Tokens sortis du wallet du dev sans passer par la courbe
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenTransfer {
+    pub signature: String,
+    pub slot: u64,
+    #[serde(serialize_with = "base58::pubkey")]
+    pub to: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DevReport {
+    // Wallet `creator` de pump.fun, qui touche les frais créateur
+    #[serde(serialize_with = "base58::pubkey")]
+    pub wallet: Pubkey,
+    // Signataire de la création quand il diffère du créateur
+    #[serde(serialize_with = "base58::option_pubkey")]
+    pub deployer: Option<Pubkey>,
+    pub initial_buy: Option<DevTrade>,
+    // L'achat initial est dans la transaction de création elle-même
+    pub initial_buy_in_create_tx: bool,
+    pub tokens_bought: u64,
+    pub sells: Vec<DevTrade>,
+    pub creator_fees: u64,
+    pub transfers: Vec<TokenTransfer>,
+    // false si l'historique du dev n'a pas été parcouru : les transferts sont alors inconnus
+    pub transfers_checked: bool,
+}
+
+impl DevReport {
+    pub fn tokens_sold(&self) -> u64 {
+        self.sells.iter().map(|s| s.token_amount).sum()
+    }
+
+    pub fn tokens_transferred(&self) -> u64 {
+        self.transfers.iter().map(|t| t.amount).sum()
+    }
+
+    // Tout ce que le dev a acheté est reparti, vendu ou transféré
+    pub fn sold_out(&self) -> bool {
+        self.tokens_bought > 0 && self.tokens_sold() + self.tokens_transferred() >= self.tokens_bought
+    }
+
+    pub fn is_dev(&self, wallet: &Pubkey) -> bool {
+        self.wallet == *wallet || self.deployer == Some(*wallet)
+    }
+}
+
+// `creator` : celui du CreateEvent, ou à défaut celui de la courbe ; `trades` en ordre chronologique
+pub fn dev_report(
+    creator: Pubkey,
+    create: Option<&CreateEvent>,
+    creation: Option<&Creation>,
+    trades: &[TradeEnvelope],
+    transfers: Option<Vec<TokenTransfer>>,
+) -> DevReport {
+    let mut report = DevReport {
+        wallet: creator,
+        deployer: create.map(|c| c.user).filter(|user| *user != creator),
+        initial_buy: None,
+        initial_buy_in_create_tx: false,
+        tokens_bought: 0,
+        sells: Vec::new(),
+        creator_fees: 0,
+        transfers_checked: transfers.is_some(),
+        transfers: transfers.unwrap_or_default(),
+    };
+
+    let launch_time = create.map(|c| c.timestamp).or_else(|| creation.and_then(|c| c.block_time).map(|t| t as u64));
+    let launch_slot = creation.map(|c| c.slot);
+    let dated = |trade: &TradeEnvelope, sol_amount: u64| DevTrade {
+        signature: trade.signature.clone(),
+        slot: trade.slot,
+        timestamp: trade.timestamp,
+        token_amount: trade.token_amount,
+        sol_amount,
+        seconds_after_launch: launch_time.map(|t| trade.timestamp.saturating_sub(t)),
+        slots_after_launch: launch_slot.map(|s| trade.slot.saturating_sub(s)),
+    };
+
+    for trade in trades {
+        if trade.creator == creator {
+            report.creator_fees += trade.creator_fee;
+        }
+        if !report.is_dev(&trade.user) {
+            continue;
+        }
+        let fees = trade.fee + trade.creator_fee;
+        if trade.is_buy {
+            report.tokens_bought += trade.token_amount;
+            if report.initial_buy.is_none() {
+                report.initial_buy_in_create_tx = creation.is_some_and(|c| c.signature == trade.signature);
+                report.initial_buy = Some(dated(trade, trade.sol_amount + fees));
+            }
+        } else {
+            report.sells.push(dated(trade, trade.sol_amount.saturating_sub(fees)));
+        }
+    }
+
+    if report.sold_out() {
+        tracing::info!(wallet = %report.wallet, sells = report.sells.len(), "🚪 le dev est sorti");
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::sample::{CREATION_SLOT, CREATION_TIME, SampleLaunch, key, signature};
+
+    fn creation(launch: &SampleLaunch) -> Creation {
+        let tx = &launch.transactions[0];
+        Creation {
+            signature: tx.signature.to_string(),
+            slot: tx.slot,
+            block_time: Some(tx.block_time),
+        }
+    }
+
+    // Trade du wallet `user` `offset` slots (et secondes) après la création
+    fn trade(user: Pubkey, is_buy: bool, offset: u64, token_amount: u64) -> TradeEnvelope {
+        let mut trade = SampleLaunch::new().trade_envelopes().remove(0);
+        trade.signature = signature(50 + offset as u8).to_string();
+        trade.slot = CREATION_SLOT + offset;
+        trade.event.user = user;
+        trade.event.is_buy = is_buy;
+        trade.event.timestamp = CREATION_TIME as u64 + offset;
+        trade.event.token_amount = token_amount;
+        trade.event.sol_amount = token_amount * 10;
+        trade.event.fee = 100;
+        trade.event.creator_fee = 50;
+        trade
+    }
+
+    fn transfer(amount: u64) -> TokenTransfer {
+        TokenTransfer {
+            signature: signature(80).to_string(),
+            slot: CREATION_SLOT + 20,
+            to: key(80),
+            amount,
+        }
+    }
+
+    #[test]
+    fn dev_buy_bundled_with_the_create_is_flagged() {
+        let launch = SampleLaunch::new();
+        let trades = launch.trade_envelopes();
+
+        let report = dev_report(launch.creator, Some(launch.create()), Some(&creation(&launch)), &trades, None);
+
+        let initial = report.initial_buy.as_ref().unwrap();
+        assert!(report.initial_buy_in_create_tx);
+        assert_eq!(initial.signature, launch.transactions[0].signature.to_string());
+        assert_eq!((initial.seconds_after_launch, initial.slots_after_launch), (Some(0), Some(0)));
+        let event = &launch.transactions[0].trades[0];
+        assert_eq!(initial.sol_amount, event.sol_amount + event.fee + event.creator_fee);
+        assert_eq!(report.tokens_bought, event.token_amount);
+        assert!(report.deployer.is_none() && !report.transfers_checked);
+        // Frais créateur de tous les trades, pas seulement ceux du dev
+        let fees: u64 = launch.transactions.iter().flat_map(|tx| &tx.trades).map(|t| t.creator_fee).sum();
+        assert_eq!(report.creator_fees, fees);
+    }
+
+    #[test]
+    fn dev_buy_in_a_later_transaction_is_not_bundled() {
+        let launch = SampleLaunch::new();
+        let trades = vec![trade(launch.creator, true, 2, 1_000), trade(launch.creator, true, 4, 500)];
+
+        let report = dev_report(launch.creator, Some(launch.create()), Some(&creation(&launch)), &trades, None);
+
+        let initial = report.initial_buy.as_ref().unwrap();
+        assert!(!report.initial_buy_in_create_tx);
+        assert_eq!((initial.seconds_after_launch, initial.slots_after_launch), (Some(2), Some(2)));
+        assert_eq!(initial.sol_amount, 10_000 + 150);
+        assert_eq!(report.tokens_bought, 1_500);
+    }
+
+    #[test]
+    fn sold_out_counts_sales_and_transfers() {
+        let creator = key(2);
+        let trades = vec![trade(creator, true, 0, 1_000), trade(creator, false, 3, 600)];
+
+        let partial = dev_report(creator, None, None, &trades, Some(Vec::new()));
+        assert_eq!(partial.tokens_sold(), 600);
+        assert_eq!(partial.sells[0].sol_amount, 6_000 - 150);
+        assert!(partial.transfers_checked && !partial.sold_out());
+
+        let out = dev_report(creator, None, None, &trades, Some(vec![transfer(400)]));
+        assert_eq!(out.tokens_transferred(), 400);
+        assert!(out.sold_out());
+
+        // Rien acheté : rien à vendre
+        let seller = dev_report(creator, None, None, &trades[1..], Some(vec![transfer(400)]));
+        assert!(!seller.sold_out());
+    }
+
+    #[test]
+    fn deployer_trades_count_as_dev_trades() {
+        let launch = SampleLaunch::new();
+        let deployer = key(60);
+        let mut create = launch.create().clone();
+        create.user = deployer;
+        let trades = vec![trade(deployer, true, 0, 1_000), trade(key(61), true, 1, 700), trade(deployer, false, 2, 1_000)];
+
+        let report = dev_report(launch.creator, Some(&create), None, &trades, None);
+
+        assert_eq!(report.deployer, Some(deployer));
+        assert!(report.is_dev(&deployer) && !report.is_dev(&key(61)));
+        assert_eq!(report.tokens_bought, 1_000);
+        assert!(report.sold_out());
+        assert_eq!(report.creator_fees, 150);
+    }
+}
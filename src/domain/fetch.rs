@@ -0,0 +1,51 @@
+use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailureKind {
+    // Le nœud a répondu `null` : transaction pas (encore) disponible
+    NotAvailable,
+    RateLimited,
+    Timeout,
+    ServerError,
+    InvalidSignature,
+    Rpc,
+    // Transaction récupérée mais ses événements ne se décodent pas
+    Undecodable,
+}
+
+impl Display for FailureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            FailureKind::NotAvailable => "not available",
+            FailureKind::RateLimited => "rate limited",
+            FailureKind::Timeout => "timeout",
+            FailureKind::ServerError => "server error",
+            FailureKind::InvalidSignature => "invalid signature",
+            FailureKind::Rpc => "rpc error",
+            FailureKind::Undecodable => "undecodable",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FetchFailure {
+    pub signature: String,
+    pub kind: FailureKind,
+    pub attempts: u32,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct FetchedTransactions {
+    // Dans l'ordre des signatures demandées
+    pub transactions: Vec<EncodedConfirmedTransactionWithStatusMeta>,
+    pub failures: Vec<FetchFailure>,
+}
+
+impl FetchedTransactions {
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
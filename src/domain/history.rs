@@ -0,0 +1,62 @@
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use serde::Serialize;
+use solana_sdk::signature::Signature;
+
+#[derive(Debug, Clone, Default)]
+pub struct HistoryLimit {
+    pub max_signatures: Option<usize>,
+    pub min_slot: Option<u64>,
+    pub max_slot: Option<u64>,
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    pub until_signature: Option<Signature>,
+    pub hard_cap: Option<usize>,
+}
+
+impl HistoryLimit {
+    // La pagination part du plus récent : une signature trop ancienne arrête le parcours
+    pub fn is_before_range(&self, s: &RpcConfirmedTransactionStatusWithSignature) -> bool {
+        if self.min_slot.is_some_and(|min| s.slot < min) {
+            return true;
+        }
+        matches!((self.since, s.block_time), (Some(since), Some(t)) if t < since)
+    }
+
+    // Signature plus récente que la plage demandée : on l'ignore mais on continue
+    pub fn is_after_range(&self, s: &RpcConfirmedTransactionStatusWithSignature) -> bool {
+        if self.max_slot.is_some_and(|max| s.slot > max) {
+            return true;
+        }
+        matches!((self.until, s.block_time), (Some(until), Some(t)) if t > until)
+    }
+
+    pub fn is_full(&self, fetched: usize) -> bool {
+        self.max_signatures.is_some_and(|max| fetched >= max)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SignatureHistory {
+    // Du plus récent au plus ancien, comme renvoyé par getSignaturesForAddress
+    pub signatures: Vec<RpcConfirmedTransactionStatusWithSignature>,
+    pub pages: usize,
+    // true si le parcours a atteint la toute première signature de l'adresse
+    pub complete: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct HistorySummary {
+    pub signatures_fetched: usize,
+    pub pages: usize,
+    pub complete: bool,
+}
+
+impl SignatureHistory {
+    pub fn summary(&self) -> HistorySummary {
+        HistorySummary {
+            signatures_fetched: self.signatures.len(),
+            pages: self.pages,
+            complete: self.complete,
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::domain::base58;
+use crate::domain::decoder::mint::TokenAccountInfo;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+// Comptes qui détiennent des tokens sans être des holders : courbe, pools, brûlage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HolderLabel {
+    BondingCurve,
+    PumpSwapPool,
+    RaydiumPool,
+    MeteoraPool,
+    Burn,
+}
+
+impl Display for HolderLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            HolderLabel::BondingCurve => "pump.fun bonding curve",
+            HolderLabel::PumpSwapPool => "PumpSwap pool",
+            HolderLabel::RaydiumPool => "Raydium pool",
+            HolderLabel::MeteoraPool => "Meteora pool",
+            HolderLabel::Burn => "burn",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Holder {
+    #[serde(serialize_with = "base58::pubkey")]
+    pub owner: Pubkey,
+    // Un owner peut avoir plusieurs comptes de token sur le même mint
+    pub token_accounts: usize,
+    pub amount: u64,
+    pub supply_share: f64,
+    pub label: Option<HolderLabel>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Concentration {
+    pub holders: usize,
+    pub top10_share: f64,
+    // 0 : répartition égale, 1 : un seul holder
+    pub gini: f64,
+    // Somme des parts au carré, entre 1/holders et 1
+    pub herfindahl: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HolderSnapshot {
+    #[serde(serialize_with = "base58::pubkey")]
+    pub mint: Pubkey,
+    pub supply: u64,
+    pub decimals: u8,
+    pub token_accounts: usize,
+    // Par solde décroissant, comptes labellisés compris
+    pub holders: Vec<Holder>,
+    // Calculée sans les comptes labellisés
+    pub concentration: Concentration,
+}
+
+impl HolderSnapshot {
+    // `labels` : owners reconnus ; les comptes vides et ceux d'un autre mint sont ignorés
+    pub fn build(mint: Pubkey, supply: u64, decimals: u8, accounts: &[TokenAccountInfo], labels: &HashMap<Pubkey, HolderLabel>) -> Self {
+        let mut by_owner: HashMap<Pubkey, Holder> = HashMap::new();
+        let mut token_accounts = 0;
+        for account in accounts.iter().filter(|a| a.mint == mint && a.amount > 0) {
+            token_accounts += 1;
+            let holder = by_owner.entry(account.owner).or_insert_with(|| Holder {
+                owner: account.owner,
+                token_accounts: 0,
+                amount: 0,
+                supply_share: 0.0,
+                label: labels.get(&account.owner).copied(),
+            });
+            holder.token_accounts += 1;
+            holder.amount += account.amount;
+        }
+
+        let mut holders: Vec<Holder> = by_owner.into_values().collect();
+        for holder in &mut holders {
+            if supply > 0 {
+                holder.supply_share = holder.amount as f64 / supply as f64;
+            }
+        }
+        holders.sort_by_key(|h| std::cmp::Reverse(h.amount));
+
+        let balances: Vec<u64> = holders.iter().filter(|h| h.label.is_none()).map(|h| h.amount).collect();
+        let concentration = Concentration {
+            holders: balances.len(),
+            top10_share: if supply > 0 {
+                balances.iter().take(10).sum::<u64>() as f64 / supply as f64
+            } else {
+                0.0
+            },
+            gini: gini(&balances),
+            herfindahl: herfindahl(&balances),
+        };
+        Self {
+            mint,
+            supply,
+            decimals,
+            token_accounts,
+            holders,
+            concentration,
+        }
+    }
+
+    pub fn labelled(&self) -> impl Iterator<Item = &Holder> {
+        self.holders.iter().filter(|h| h.label.is_some())
+    }
+}
+
+// `balances` par ordre décroissant
+fn gini(balances: &[u64]) -> f64 {
+    let n = balances.len();
+    let total: u128 = balances.iter().map(|b| *b as u128).sum();
+    if n < 2 || total == 0 {
+        return 0.0;
+    }
+    // Formule sur les soldes triés par ordre croissant : Σ (2i - n - 1) x_i / (n Σ x)
+    let weighted: f64 = balances
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| (2.0 * (i + 1) as f64 - n as f64 - 1.0) * *b as f64)
+        .sum();
+    weighted / (n as f64 * total as f64)
+}
+
+fn herfindahl(balances: &[u64]) -> f64 {
+    let total: u128 = balances.iter().map(|b| *b as u128).sum();
+    if total == 0 {
+        return 0.0;
+    }
+    balances
+        .iter()
+        .map(|b| {
+            let share = *b as f64 / total as f64;
+            share * share
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::sample::key;
+
+    fn account(mint: Pubkey, owner: Pubkey, amount: u64) -> TokenAccountInfo {
+        TokenAccountInfo { mint, owner, amount }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn gini_ranges_from_equal_to_concentrated() {
+        assert_eq!(gini(&[5, 5, 5, 5]), 0.0);
+        assert_eq!(gini(&[100]), 0.0);
+        assert_eq!(gini(&[]), 0.0);
+        assert_eq!(gini(&[3, 1]), 0.25);
+        // Un seul holder non nul parmi n : (n - 1) / n
+        assert_eq!(gini(&[10, 0, 0, 0]), 0.75);
+        assert_close(gini(&[300, 200, 100]), 2.0 / 9.0);
+    }
+
+    #[test]
+    fn herfindahl_sums_squared_shares() {
+        assert_eq!(herfindahl(&[100]), 1.0);
+        assert_eq!(herfindahl(&[5, 5, 5, 5]), 0.25);
+        assert_eq!(herfindahl(&[3, 1]), 0.625);
+        assert_eq!(herfindahl(&[]), 0.0);
+        assert_eq!(herfindahl(&[0, 0]), 0.0);
+    }
+
+    #[test]
+    fn snapshot_merges_owners_and_excludes_labelled_accounts() {
+        let mint = key(1);
+        let [curve, a, b, c, d] = [key(10), key(11), key(12), key(13), key(14)];
+        let accounts = vec![
+            account(mint, curve, 500),
+            account(mint, a, 150),
+            account(mint, b, 300),
+            account(mint, a, 50),
+            account(mint, c, 100),
+            // Compte vide et compte d'un autre mint : ignorés
+            account(mint, d, 0),
+            account(key(2), d, 999),
+        ];
+        let labels = HashMap::from([(curve, HolderLabel::BondingCurve)]);
+
+        let snapshot = HolderSnapshot::build(mint, 1_000, 6, &accounts, &labels);
+
+        assert_eq!(snapshot.token_accounts, 5);
+        let holders: Vec<(Pubkey, usize, u64)> = snapshot.holders.iter().map(|h| (h.owner, h.token_accounts, h.amount)).collect();
+        assert_eq!(holders, vec![(curve, 1, 500), (b, 1, 300), (a, 2, 200), (c, 1, 100)]);
+        assert_eq!(snapshot.holders[2].supply_share, 0.2);
+        let labelled: Vec<Pubkey> = snapshot.labelled().map(|h| h.owner).collect();
+        assert_eq!(labelled, vec![curve]);
+
+        // Concentration sur 300 / 200 / 100, sans la courbe
+        let concentration = snapshot.concentration;
+        assert_eq!(concentration.holders, 3);
+        assert_eq!(concentration.top10_share, 0.6);
+        assert_close(concentration.gini, 2.0 / 9.0);
+        assert_close(concentration.herfindahl, 14.0 / 36.0);
+    }
+
+    #[test]
+    fn single_unlabelled_holder_is_fully_concentrated() {
+        let mint = key(1);
+        let accounts = vec![account(mint, key(10), 700), account(mint, key(11), 300)];
+        let labels = HashMap::from([(key(10), HolderLabel::PumpSwapPool)]);
+
+        let concentration = HolderSnapshot::build(mint, 1_000, 6, &accounts, &labels).concentration;
+
+        assert_eq!((concentration.holders, concentration.gini, concentration.herfindahl), (1, 0.0, 1.0));
+        assert_eq!(concentration.top10_share, 0.3);
+    }
+}
@@ -0,0 +1,402 @@
+use crate::domain::base58;
+use crate::domain::units::LAMPORTS_PER_SOL;
+use crate::platforms::pumpfun::curve::CurveState;
+use crate::platforms::pumpfun::events::{TradeEnvelope, TradeEvent};
+use polars::prelude::*;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostMethod {
+    Fifo,
+    Average,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerSort {
+    Total,
+    Realized,
+    Unrealized,
+    Spent,
+}
+
+// PnL en lamports selon une méthode de coût
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Pnl {
+    pub realized: i128,
+    pub unrealized: i128,
+}
+
+impl Pnl {
+    pub fn total(&self) -> i128 {
+        self.realized + self.unrealized
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct FifoBasis {
+    // Lots encore détenus : (tokens, coût en lamports)
+    lots: VecDeque<(u64, u128)>,
+    realized: i128,
+}
+
+impl FifoBasis {
+    fn buy(&mut self, tokens: u64, cost: u128) {
+        self.lots.push_back((tokens, cost));
+    }
+
+    // Renvoie la quantité effectivement adossée à des achats connus
+    fn sell(&mut self, tokens: u64, proceeds: u128) -> u64 {
+        let mut left = tokens;
+        let mut matched_cost = 0u128;
+        while left > 0 {
+            let Some((lot_tokens, lot_cost)) = self.lots.front_mut() else {
+                break;
+            };
+            let take = left.min(*lot_tokens);
+            let cost = *lot_cost * take as u128 / *lot_tokens as u128;
+            *lot_tokens -= take;
+            *lot_cost -= cost;
+            matched_cost += cost;
+            left -= take;
+            if *lot_tokens == 0 {
+                self.lots.pop_front();
+            }
+        }
+        let matched = tokens - left;
+        self.realized += share(proceeds, matched, tokens) as i128 - matched_cost as i128;
+        matched
+    }
+
+    fn remaining_cost(&self) -> u128 {
+        self.lots.iter().map(|(_, cost)| cost).sum()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct AverageBasis {
+    tokens: u64,
+    cost: u128,
+    realized: i128,
+}
+
+impl AverageBasis {
+    fn buy(&mut self, tokens: u64, cost: u128) {
+        self.tokens += tokens;
+        self.cost += cost;
+    }
+
+    fn sell(&mut self, tokens: u64, proceeds: u128) {
+        let matched = tokens.min(self.tokens);
+        if matched == 0 {
+            return;
+        }
+        let cost = self.cost * matched as u128 / self.tokens as u128;
+        self.tokens -= matched;
+        self.cost -= cost;
+        self.realized += share(proceeds, matched, tokens) as i128 - cost as i128;
+    }
+}
+
+// Position d'un wallet sur le mint, montants en lamports et unités de base
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletPosition {
+    #[serde(serialize_with = "base58::pubkey")]
+    pub wallet: Pubkey,
+    pub buys: usize,
+    pub sells: usize,
+    pub tokens_bought: u64,
+    pub tokens_sold: u64,
+    // Tokens vendus sans achat visible dans la fenêtre (achetés avant ou reçus par transfert)
+    pub tokens_sold_unmatched: u64,
+    // SOL payé frais compris / reçu net de frais
+    pub sol_spent: u64,
+    pub sol_received: u64,
+    pub first_trade: u64,
+    pub last_trade: u64,
+    #[serde(skip)]
+    fifo: FifoBasis,
+    #[serde(skip)]
+    average: AverageBasis,
+    #[serde(skip)]
+    fifo_unrealized: i128,
+    #[serde(skip)]
+    average_unrealized: i128,
+}
+
+impl WalletPosition {
+    fn new(wallet: Pubkey, timestamp: u64) -> Self {
+        Self {
+            wallet,
+            buys: 0,
+            sells: 0,
+            tokens_bought: 0,
+            tokens_sold: 0,
+            tokens_sold_unmatched: 0,
+            sol_spent: 0,
+            sol_received: 0,
+            first_trade: timestamp,
+            last_trade: timestamp,
+            fifo: FifoBasis::default(),
+            average: AverageBasis::default(),
+            fifo_unrealized: 0,
+            average_unrealized: 0,
+        }
+    }
+
+    fn push(&mut self, trade: &TradeEvent) {
+        let fees = trade.fee + trade.creator_fee;
+        self.last_trade = trade.timestamp;
+        if trade.is_buy {
+            let cost = trade.sol_amount + fees;
+            self.buys += 1;
+            self.tokens_bought += trade.token_amount;
+            self.sol_spent += cost;
+            self.fifo.buy(trade.token_amount, cost as u128);
+            self.average.buy(trade.token_amount, cost as u128);
+        } else {
+            let proceeds = trade.sol_amount.saturating_sub(fees);
+            self.sells += 1;
+            self.tokens_sold += trade.token_amount;
+            self.sol_received += proceeds;
+            let matched = self.fifo.sell(trade.token_amount, proceeds as u128);
+            self.tokens_sold_unmatched += trade.token_amount - matched;
+            self.average.sell(trade.token_amount, proceeds as u128);
+        }
+    }
+
+    // Valorise les tokens restants au prix spot de la courbe
+    fn mark(&mut self, curve: &CurveState) {
+        let value = |tokens: u64| -> u128 {
+            if curve.virtual_token_reserves == 0 {
+                return 0;
+            }
+            tokens as u128 * curve.virtual_sol_reserves as u128 / curve.virtual_token_reserves as u128
+        };
+        let held: u64 = self.fifo.lots.iter().map(|(tokens, _)| tokens).sum();
+        self.fifo_unrealized = value(held) as i128 - self.fifo.remaining_cost() as i128;
+        self.average_unrealized = value(self.average.tokens) as i128 - self.average.cost as i128;
+    }
+
+    // Solde de tokens issu des trades de la fenêtre
+    pub fn position(&self) -> u64 {
+        self.tokens_bought.saturating_sub(self.tokens_sold - self.tokens_sold_unmatched)
+    }
+
+    pub fn pnl(&self, method: CostMethod) -> Pnl {
+        match method {
+            CostMethod::Fifo => Pnl {
+                realized: self.fifo.realized,
+                unrealized: self.fifo_unrealized,
+            },
+            CostMethod::Average => Pnl {
+                realized: self.average.realized,
+                unrealized: self.average_unrealized,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WalletLedger {
+    pub positions: Vec<WalletPosition>,
+    // État de la courbe utilisé pour valoriser les positions ouvertes
+    pub mark: Option<CurveState>,
+}
+
+impl WalletLedger {
+    // `mark` : état courant de la courbe ; à défaut, les réserves après le dernier trade
+    pub fn build(trades: &[TradeEnvelope], mark: Option<CurveState>) -> Self {
+        let mut by_wallet: HashMap<Pubkey, WalletPosition> = HashMap::new();
+        for trade in trades {
+            by_wallet
+                .entry(trade.user)
+                .or_insert_with(|| WalletPosition::new(trade.user, trade.timestamp))
+                .push(trade);
+        }
+
+        let mark = mark.or_else(|| trades.last().map(|t| CurveState::from(&t.event)));
+        let mut positions: Vec<WalletPosition> = by_wallet.into_values().collect();
+        if let Some(curve) = &mark {
+            positions.iter_mut().for_each(|p| p.mark(curve));
+        }
+        positions.sort_by_key(|p| p.first_trade);
+        Self { positions, mark }
+    }
+
+    pub fn get(&self, wallet: &Pubkey) -> Option<&WalletPosition> {
+        self.positions.iter().find(|p| p.wallet == *wallet)
+    }
+
+    // Du plus gagnant au plus perdant (ou du plus gros acheteur pour `Spent`)
+    pub fn sort(&mut self, by: LedgerSort, method: CostMethod) {
+        self.positions.sort_by_key(|p| {
+            let pnl = p.pnl(method);
+            std::cmp::Reverse(match by {
+                LedgerSort::Total => pnl.total(),
+                LedgerSort::Realized => pnl.realized,
+                LedgerSort::Unrealized => pnl.unrealized,
+                LedgerSort::Spent => p.sol_spent as i128,
+            })
+        });
+    }
+
+    pub fn frame(&self, method: CostMethod) -> PolarsResult<DataFrame> {
+        let sol = |lamports: i128| lamports as f64 / LAMPORTS_PER_SOL;
+        let rows = &self.positions;
+        df!(
+            "wallet" => rows.iter().map(|p| p.wallet.to_string()).collect::<Vec<_>>(),
+            "buys" => rows.iter().map(|p| p.buys as u32).collect::<Vec<_>>(),
+            "sells" => rows.iter().map(|p| p.sells as u32).collect::<Vec<_>>(),
+            "position" => rows.iter().map(|p| p.position()).collect::<Vec<_>>(),
+            "tokens_sold_unmatched" => rows.iter().map(|p| p.tokens_sold_unmatched).collect::<Vec<_>>(),
+            "sol_spent" => rows.iter().map(|p| sol(p.sol_spent as i128)).collect::<Vec<_>>(),
+            "sol_received" => rows.iter().map(|p| sol(p.sol_received as i128)).collect::<Vec<_>>(),
+            "realized_sol" => rows.iter().map(|p| sol(p.pnl(method).realized)).collect::<Vec<_>>(),
+            "unrealized_sol" => rows.iter().map(|p| sol(p.pnl(method).unrealized)).collect::<Vec<_>>(),
+            "total_sol" => rows.iter().map(|p| sol(p.pnl(method).total())).collect::<Vec<_>>(),
+            "first_trade" => rows.iter().map(|p| p.first_trade).collect::<Vec<_>>(),
+            "last_trade" => rows.iter().map(|p| p.last_trade).collect::<Vec<_>>(),
+        )
+    }
+}
+
+// Part du produit d'une vente adossée à un coût connu ; le reste n'entre pas dans le PnL réalisé
+fn share(proceeds: u128, matched: u64, total: u64) -> u128 {
+    if total == 0 {
+        return 0;
+    }
+    proceeds * matched as u128 / total as u128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::sample::{SampleLaunch, key};
+
+    // Trade sans frais aux montants choisis ; les réserves sont celles d'un vrai trade
+    fn trade(wallet: Pubkey, is_buy: bool, sol_amount: u64, token_amount: u64) -> TradeEnvelope {
+        let mut trade = SampleLaunch::new().trade_envelopes().remove(0);
+        trade.event.user = wallet;
+        trade.event.is_buy = is_buy;
+        trade.event.sol_amount = sol_amount;
+        trade.event.token_amount = token_amount;
+        trade.event.fee = 0;
+        trade.event.creator_fee = 0;
+        trade
+    }
+
+    // Prix spot de 40 lamports par unité de token
+    fn mark() -> CurveState {
+        CurveState {
+            virtual_sol_reserves: 40,
+            virtual_token_reserves: 1,
+            real_sol_reserves: 0,
+            real_token_reserves: 0,
+            token_total_supply: 0,
+            complete: false,
+        }
+    }
+
+    #[test]
+    fn a_sale_consumes_the_oldest_lots_first() {
+        let wallet = key(3);
+        let trades = [
+            trade(wallet, true, 1_000, 100),
+            trade(wallet, true, 3_000, 100),
+            trade(wallet, false, 3_000, 150),
+        ];
+
+        let ledger = WalletLedger::build(&trades, Some(mark()));
+        let position = ledger.get(&wallet).unwrap();
+
+        assert_eq!(position.position(), 50);
+        assert_eq!(position.tokens_sold_unmatched, 0);
+        // FIFO : le premier lot entier (1 000) et la moitié du second (1 500)
+        assert_eq!(position.pnl(CostMethod::Fifo), Pnl { realized: 500, unrealized: 2_000 - 1_500 });
+        // Coût moyen de 20 par token
+        assert_eq!(position.pnl(CostMethod::Average), Pnl { realized: 0, unrealized: 2_000 - 1_000 });
+    }
+
+    #[test]
+    fn tokens_sold_beyond_the_buys_stay_out_of_the_realized_pnl() {
+        let wallet = key(3);
+        let trades = [trade(wallet, true, 1_000, 100), trade(wallet, false, 3_000, 150)];
+
+        let ledger = WalletLedger::build(&trades, Some(mark()));
+        let position = ledger.get(&wallet).unwrap();
+
+        assert_eq!(position.tokens_sold_unmatched, 50);
+        assert_eq!(position.position(), 0);
+        // Seuls les deux tiers du produit sont adossés à un achat
+        for method in [CostMethod::Fifo, CostMethod::Average] {
+            assert_eq!(position.pnl(method), Pnl { realized: 1_000, unrealized: 0 });
+        }
+    }
+
+    #[test]
+    fn a_wallet_that_only_sells_has_no_pnl() {
+        let wallet = key(4);
+        let ledger = WalletLedger::build(&[trade(wallet, false, 3_000, 150)], Some(mark()));
+        let position = ledger.get(&wallet).unwrap();
+
+        assert_eq!(position.tokens_sold_unmatched, 150);
+        assert_eq!(position.sol_received, 3_000);
+        assert_eq!(position.pnl(CostMethod::Fifo), Pnl::default());
+        assert_eq!(position.pnl(CostMethod::Average), Pnl::default());
+    }
+
+    #[test]
+    fn fees_are_part_of_the_cost_and_come_off_the_proceeds() {
+        let wallet = key(3);
+        let mut buy = trade(wallet, true, 1_000, 100);
+        buy.event.fee = 10;
+        buy.event.creator_fee = 5;
+        let mut sell = trade(wallet, false, 2_000, 100);
+        sell.event.fee = 20;
+        sell.event.creator_fee = 10;
+
+        let ledger = WalletLedger::build(&[buy, sell], Some(mark()));
+        let position = ledger.get(&wallet).unwrap();
+
+        assert_eq!(position.sol_spent, 1_015);
+        assert_eq!(position.sol_received, 1_970);
+        assert_eq!(position.pnl(CostMethod::Fifo).realized, 1_970 - 1_015);
+    }
+
+    #[test]
+    fn open_positions_are_marked_at_the_last_trade_without_a_curve() {
+        let wallet = key(3);
+        let buy = trade(wallet, true, 1_000, 1_000_000);
+        let spot = |tokens: u128| {
+            tokens * buy.virtual_sol_reserves as u128 / buy.virtual_token_reserves as u128
+        };
+        let expected = spot(1_000_000) as i128 - 1_000;
+
+        let ledger = WalletLedger::build(std::slice::from_ref(&buy), None);
+        let position = ledger.get(&wallet).unwrap();
+
+        assert_eq!(ledger.mark, Some(CurveState::from(&buy.event)));
+        assert_eq!(position.pnl(CostMethod::Fifo), Pnl { realized: 0, unrealized: expected });
+        assert_eq!(position.pnl(CostMethod::Average), Pnl { realized: 0, unrealized: expected });
+    }
+
+    #[test]
+    fn sorts_from_the_biggest_winner() {
+        let (winner, loser) = (key(3), key(4));
+        let trades = [
+            trade(loser, true, 1_000, 100),
+            trade(winner, true, 1_000, 100),
+            trade(loser, false, 500, 100),
+            trade(winner, false, 2_000, 100),
+        ];
+
+        let mut ledger = WalletLedger::build(&trades, Some(mark()));
+        ledger.sort(LedgerSort::Realized, CostMethod::Fifo);
+
+        let order: Vec<Pubkey> = ledger.positions.iter().map(|p| p.wallet).collect();
+        assert_eq!(order, vec![winner, loser]);
+    }
+}
@@ -1,3 +1,16 @@
 pub mod analysis;
+pub mod base58;
+pub mod batch;
+pub mod bundles;
+pub mod candles;
+pub mod creator_history;
 pub mod decoder;
-pub mod token_stats;
\ No newline at end of file
+pub mod dev;
+pub mod fetch;
+pub mod history;
+pub mod holders;
+pub mod ledger;
+pub mod snipers;
+pub mod token_stats;
+pub mod trades;
+pub mod units;
\ No newline at end of file
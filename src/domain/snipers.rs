@@ -0,0 +1,285 @@
+use crate::domain::base58;
+use crate::platforms::pumpfun::events::{CreateEvent, TradeEnvelope};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+// Durée moyenne d'un slot, pour convertir une tolérance en slots quand seul le timestamp est connu
+const SLOT_DURATION_MS: u64 = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SniperOptions {
+    // Un achat à moins de `max_slot_delay` slots de la création est un snipe
+    pub max_slot_delay: u64,
+    // Nombre d'achats « retail » (hors snipers) qui forment la première vague
+    pub first_wave: usize,
+}
+
+impl Default for SniperOptions {
+    fn default() -> Self {
+        Self {
+            max_slot_delay: 2,
+            first_wave: 20,
+        }
+    }
+}
+
+// Point de référence de la création : le slot si on l'a, sinon le timestamp du CreateEvent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchReference {
+    Slot(u64),
+    Timestamp(u64),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Sniper {
+    #[serde(serialize_with = "base58::pubkey")]
+    pub wallet: Pubkey,
+    pub is_creator: bool,
+    // Écart en slots avec la création (None si seule l'horloge est connue)
+    pub slot_delay: Option<u64>,
+    pub tokens_bought: u64,
+    pub sol_spent: u64,
+    pub supply_share: f64,
+    pub tokens_sold: u64,
+    pub sol_received: u64,
+    pub first_sell: Option<u64>,
+    // A vendu pendant que la première vague d'acheteurs entrait
+    pub sold_into_first_wave: bool,
+}
+
+impl Sniper {
+    // Part des tokens snipés déjà revendue
+    pub fn sold_ratio(&self) -> f64 {
+        if self.tokens_bought == 0 {
+            return 0.0;
+        }
+        (self.tokens_sold as f64 / self.tokens_bought as f64).min(1.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SniperReport {
+    pub reference: Option<LaunchReference>,
+    pub options: SniperOptions,
+    pub snipers: Vec<Sniper>,
+    // Slot du dernier achat de la première vague retail
+    pub first_wave_end: Option<u64>,
+}
+
+impl SniperReport {
+    pub fn supply_share(&self) -> f64 {
+        self.snipers.iter().map(|s| s.supply_share).sum()
+    }
+
+    pub fn sold_into_first_wave(&self) -> usize {
+        self.snipers.iter().filter(|s| s.sold_into_first_wave).count()
+    }
+}
+
+// `trades` en ordre chronologique, `creation_slot` issu du preflight ou du slot de la transaction de création
+pub fn detect_snipers(
+    trades: &[TradeEnvelope],
+    create: Option<&CreateEvent>,
+    creation_slot: Option<u64>,
+    total_supply: u64,
+    options: SniperOptions,
+) -> SniperReport {
+    let reference = creation_slot
+        .map(LaunchReference::Slot)
+        .or_else(|| create.map(|c| LaunchReference::Timestamp(c.timestamp)));
+    let mut report = SniperReport {
+        reference,
+        options,
+        snipers: Vec::new(),
+        first_wave_end: None,
+    };
+    let Some(reference) = reference else {
+        return report;
+    };
+
+    let is_snipe = |trade: &TradeEnvelope| -> bool {
+        match reference {
+            LaunchReference::Slot(slot) => trade.slot >= slot && trade.slot - slot <= options.max_slot_delay,
+            LaunchReference::Timestamp(ts) => {
+                let max_seconds = (options.max_slot_delay * SLOT_DURATION_MS).div_ceil(1_000);
+                trade.timestamp >= ts && trade.timestamp - ts <= max_seconds
+            }
+        }
+    };
+
+    let mut snipers: HashMap<Pubkey, Sniper> = HashMap::new();
+    let mut order: Vec<Pubkey> = Vec::new();
+    for trade in trades.iter().filter(|t| t.is_buy && is_snipe(t)) {
+        let sniper = snipers.entry(trade.user).or_insert_with(|| {
+            order.push(trade.user);
+            Sniper {
+                wallet: trade.user,
+                is_creator: create.is_some_and(|c| c.creator == trade.user),
+                slot_delay: match reference {
+                    LaunchReference::Slot(slot) => Some(trade.slot - slot),
+                    LaunchReference::Timestamp(_) => None,
+                },
+                tokens_bought: 0,
+                sol_spent: 0,
+                supply_share: 0.0,
+                tokens_sold: 0,
+                sol_received: 0,
+                first_sell: None,
+                sold_into_first_wave: false,
+            }
+        });
+        sniper.tokens_bought += trade.token_amount;
+        sniper.sol_spent += trade.sol_amount + trade.fee + trade.creator_fee;
+    }
+
+    // Première vague : les premiers achats retail après la fenêtre de snipe
+    report.first_wave_end = trades
+        .iter()
+        .filter(|t| t.is_buy && !snipers.contains_key(&t.user) && !is_snipe(t))
+        .take(options.first_wave)
+        .last()
+        .map(|t| t.slot);
+
+    for trade in trades.iter().filter(|t| !t.is_buy) {
+        let Some(sniper) = snipers.get_mut(&trade.user) else {
+            continue;
+        };
+        sniper.tokens_sold += trade.token_amount;
+        sniper.sol_received += trade.sol_amount.saturating_sub(trade.fee + trade.creator_fee);
+        sniper.first_sell.get_or_insert(trade.slot);
+        if report.first_wave_end.is_some_and(|end| trade.slot <= end) {
+            sniper.sold_into_first_wave = true;
+        }
+    }
+
+    report.snipers = order
+        .into_iter()
+        .filter_map(|wallet| snipers.remove(&wallet))
+        .map(|mut sniper| {
+            if total_supply > 0 {
+                sniper.supply_share = sniper.tokens_bought as f64 / total_supply as f64;
+            }
+            sniper
+        })
+        .collect();
+    if !report.snipers.is_empty() {
+        tracing::info!(
+            snipers = report.snipers.len(),
+            supply_share = report.supply_share(),
+            "🎯 snipers détectés"
+        );
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::curve::TOKEN_TOTAL_SUPPLY;
+    use crate::platforms::pumpfun::sample::{CREATION_SLOT, CREATION_TIME, SampleLaunch, key};
+
+    fn options(max_slot_delay: u64, first_wave: usize) -> SniperOptions {
+        SniperOptions { max_slot_delay, first_wave }
+    }
+
+    fn wallets(report: &SniperReport) -> Vec<Pubkey> {
+        report.snipers.iter().map(|s| s.wallet).collect()
+    }
+
+    // Trade sans frais `offset` slots après la création
+    fn trade(wallet: Pubkey, is_buy: bool, offset: u64, token_amount: u64) -> TradeEnvelope {
+        let mut trade = SampleLaunch::new().trade_envelopes().remove(0);
+        trade.slot = CREATION_SLOT + offset;
+        trade.event.user = wallet;
+        trade.event.is_buy = is_buy;
+        trade.event.timestamp = CREATION_TIME as u64 + offset;
+        trade.event.token_amount = token_amount;
+        trade.event.sol_amount = token_amount * 10;
+        trade.event.fee = 0;
+        trade.event.creator_fee = 0;
+        trade
+    }
+
+    #[test]
+    fn buys_within_the_slot_window_are_snipes() {
+        let launch = SampleLaunch::new();
+        let trades = launch.trade_envelopes();
+        let [sniper, early, late] = launch.buyers;
+
+        let report = detect_snipers(&trades, Some(launch.create()), Some(CREATION_SLOT), TOKEN_TOTAL_SUPPLY, options(2, 20));
+
+        assert_eq!(report.reference, Some(LaunchReference::Slot(CREATION_SLOT)));
+        assert_eq!(wallets(&report), vec![launch.creator, sniper, early]);
+        assert!(!wallets(&report).contains(&late));
+        assert!(report.snipers[0].is_creator && !report.snipers[1].is_creator);
+        let delays: Vec<Option<u64>> = report.snipers.iter().map(|s| s.slot_delay).collect();
+        assert_eq!(delays, vec![Some(0), Some(0), Some(1)]);
+
+        let tight = detect_snipers(&trades, Some(launch.create()), Some(CREATION_SLOT), TOKEN_TOTAL_SUPPLY, options(0, 20));
+        assert_eq!(wallets(&tight), vec![launch.creator, sniper]);
+    }
+
+    #[test]
+    fn without_a_creation_slot_the_window_falls_back_to_timestamps() {
+        let launch = SampleLaunch::new();
+        let mut trades = launch.trade_envelopes();
+        // Slots inconnus ou faux : seule l'horloge compte
+        trades.iter_mut().for_each(|t| t.slot = 0);
+
+        let report = detect_snipers(&trades, Some(launch.create()), None, TOKEN_TOTAL_SUPPLY, options(2, 20));
+
+        assert_eq!(report.reference, Some(LaunchReference::Timestamp(CREATION_TIME as u64)));
+        // 2 slots de 400 ms arrondis à 1 seconde
+        assert_eq!(wallets(&report), vec![launch.creator, launch.buyers[0], launch.buyers[1]]);
+        assert!(report.snipers.iter().all(|s| s.slot_delay.is_none()));
+
+        let none = detect_snipers(&trades, None, None, TOKEN_TOTAL_SUPPLY, options(2, 20));
+        assert_eq!(none.reference, None);
+        assert!(none.snipers.is_empty());
+    }
+
+    #[test]
+    fn the_first_wave_ends_with_the_nth_retail_buy() {
+        let [sniper, a, b, c] = [key(10), key(11), key(12), key(13)];
+        let trades = [
+            trade(sniper, true, 0, 1_000),
+            trade(a, true, 3, 100),
+            // Un sniper qui rachète plus tard ne fait pas partie de la vague
+            trade(sniper, true, 4, 100),
+            trade(b, true, 6, 100),
+            trade(c, true, 9, 100),
+        ];
+
+        let report = detect_snipers(&trades, None, Some(CREATION_SLOT), TOKEN_TOTAL_SUPPLY, options(2, 2));
+
+        assert_eq!(wallets(&report), vec![sniper]);
+        assert_eq!(report.snipers[0].tokens_bought, 1_000);
+        assert_eq!(report.first_wave_end, Some(CREATION_SLOT + 6));
+    }
+
+    #[test]
+    fn selling_before_the_first_wave_ends_is_flagged() {
+        let [dumper, holder, retail] = [key(10), key(11), key(12)];
+        let trades = [
+            trade(dumper, true, 0, 1_000),
+            trade(holder, true, 1, 1_000),
+            trade(dumper, false, 3, 600),
+            trade(retail, true, 5, 100),
+            trade(holder, false, 8, 1_000),
+        ];
+
+        let report = detect_snipers(&trades, None, Some(CREATION_SLOT), 10_000, options(2, 1));
+
+        assert_eq!(report.first_wave_end, Some(CREATION_SLOT + 5));
+        let [dumper, holder] = [&report.snipers[0], &report.snipers[1]];
+        assert!(dumper.sold_into_first_wave);
+        assert_eq!((dumper.tokens_sold, dumper.sol_received, dumper.first_sell), (600, 6_000, Some(CREATION_SLOT + 3)));
+        assert_eq!(dumper.sold_ratio(), 0.6);
+        assert!(!holder.sold_into_first_wave);
+        assert_eq!(holder.first_sell, Some(CREATION_SLOT + 8));
+        assert_eq!(report.sold_into_first_wave(), 1);
+        assert_eq!(report.supply_share(), 0.2);
+    }
+}
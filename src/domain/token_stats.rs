@@ -1,11 +1,11 @@
-use crate::platforms::pumpfun::events::TradeEvent;
+use crate::domain::units::LAMPORTS_PER_SOL;
+use crate::platforms::pumpfun::events::TradeEnvelope;
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use solana_sdk::pubkey::Pubkey;
 
-const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
-
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct TokenStats {
     pub total_trades: usize,
     pub total_volume_sol: f64,
@@ -30,7 +30,7 @@ pub struct TokenStats {
 }
 
 impl TokenStats {
-    pub fn new(trades: &Vec<TradeEvent>) -> Self {
+    pub fn new(trades: &[TradeEnvelope]) -> Self {
         // --- agrégats "historiques" (inchangés) ---
         let mut total_trades = 0usize;
         let mut total_lamports: u128 = 0;
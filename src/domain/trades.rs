@@ -0,0 +1,99 @@
+use crate::domain::units::{LAMPORTS_PER_SOL, TOKEN_UNIT};
+use crate::platforms::pumpfun::curve::CurveState;
+use crate::platforms::pumpfun::events::TradeEnvelope;
+use polars::prelude::*;
+
+// Une ligne par trade : montants bruts en entiers, et leur équivalent SOL / tokens entiers en flottant.
+// Les clés sont écrites en base58 pour rester lisibles depuis DuckDB ou pandas.
+pub fn trades_frame(trades: &[TradeEnvelope]) -> PolarsResult<DataFrame> {
+    let sol = |lamports: u64| lamports as f64 / LAMPORTS_PER_SOL;
+    let tokens = |amount: u64| amount as f64 / TOKEN_UNIT;
+    df!(
+        "signature" => trades.iter().map(|t| t.signature.clone()).collect::<Vec<_>>(),
+        "slot" => trades.iter().map(|t| t.slot).collect::<Vec<_>>(),
+        "block_time" => trades.iter().map(|t| t.block_time).collect::<Vec<_>>(),
+        "tx_index" => trades.iter().map(|t| t.tx_index).collect::<Vec<_>>(),
+        "event_index" => trades.iter().map(|t| t.event_index).collect::<Vec<_>>(),
+        "timestamp" => trades.iter().map(|t| t.timestamp).collect::<Vec<_>>(),
+        "mint" => trades.iter().map(|t| t.mint.to_string()).collect::<Vec<_>>(),
+        "user" => trades.iter().map(|t| t.user.to_string()).collect::<Vec<_>>(),
+        "is_buy" => trades.iter().map(|t| t.is_buy).collect::<Vec<_>>(),
+        "sol_amount" => trades.iter().map(|t| t.sol_amount).collect::<Vec<_>>(),
+        "sol" => trades.iter().map(|t| sol(t.sol_amount)).collect::<Vec<_>>(),
+        "token_amount" => trades.iter().map(|t| t.token_amount).collect::<Vec<_>>(),
+        "tokens" => trades.iter().map(|t| tokens(t.token_amount)).collect::<Vec<_>>(),
+        "price_sol" => trades.iter().map(|t| CurveState::from(&t.event).spot_price()).collect::<Vec<_>>(),
+        "fee" => trades.iter().map(|t| t.fee).collect::<Vec<_>>(),
+        "fee_sol" => trades.iter().map(|t| sol(t.fee)).collect::<Vec<_>>(),
+        "fee_basis_points" => trades.iter().map(|t| t.fee_basis_points as u32).collect::<Vec<_>>(),
+        "fee_recipient" => trades.iter().map(|t| t.fee_recipient.to_string()).collect::<Vec<_>>(),
+        "creator" => trades.iter().map(|t| t.creator.to_string()).collect::<Vec<_>>(),
+        "creator_fee" => trades.iter().map(|t| t.creator_fee).collect::<Vec<_>>(),
+        "creator_fee_sol" => trades.iter().map(|t| sol(t.creator_fee)).collect::<Vec<_>>(),
+        "creator_fee_basis_points" => trades.iter().map(|t| t.creator_fee_basis_points as u32).collect::<Vec<_>>(),
+        "virtual_sol_reserves" => trades.iter().map(|t| t.virtual_sol_reserves).collect::<Vec<_>>(),
+        "virtual_token_reserves" => trades.iter().map(|t| t.virtual_token_reserves).collect::<Vec<_>>(),
+        "real_sol_reserves" => trades.iter().map(|t| t.real_sol_reserves).collect::<Vec<_>>(),
+        "real_token_reserves" => trades.iter().map(|t| t.real_token_reserves).collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::sample::SampleLaunch;
+
+    #[test]
+    fn columns_keep_integer_amounts_float_values_and_string_keys() {
+        let launch = SampleLaunch::new();
+        let trades = launch.trade_envelopes();
+
+        let frame = trades_frame(&trades).unwrap();
+
+        let schema: Vec<(String, DataType)> = frame
+            .get_columns()
+            .iter()
+            .map(|c| (c.name().to_string(), c.dtype().clone()))
+            .collect();
+        let expected = [
+            ("signature", DataType::String),
+            ("slot", DataType::UInt64),
+            ("block_time", DataType::Int64),
+            ("tx_index", DataType::UInt32),
+            ("event_index", DataType::UInt32),
+            ("timestamp", DataType::UInt64),
+            ("mint", DataType::String),
+            ("user", DataType::String),
+            ("is_buy", DataType::Boolean),
+            ("sol_amount", DataType::UInt64),
+            ("sol", DataType::Float64),
+            ("token_amount", DataType::UInt64),
+            ("tokens", DataType::Float64),
+            ("price_sol", DataType::Float64),
+            ("fee", DataType::UInt64),
+            ("fee_sol", DataType::Float64),
+            ("fee_basis_points", DataType::UInt32),
+            ("fee_recipient", DataType::String),
+            ("creator", DataType::String),
+            ("creator_fee", DataType::UInt64),
+            ("creator_fee_sol", DataType::Float64),
+            ("creator_fee_basis_points", DataType::UInt32),
+            ("virtual_sol_reserves", DataType::UInt64),
+            ("virtual_token_reserves", DataType::UInt64),
+            ("real_sol_reserves", DataType::UInt64),
+            ("real_token_reserves", DataType::UInt64),
+        ]
+        .map(|(name, dtype)| (name.to_string(), dtype));
+        assert_eq!(schema, expected);
+        assert_eq!(frame.height(), trades.len());
+
+        // Montants bruts inchangés, clés en base58
+        let first = &trades[0];
+        assert_eq!(frame.column("sol_amount").unwrap().u64().unwrap().get(0), Some(first.sol_amount));
+        assert_eq!(frame.column("sol").unwrap().f64().unwrap().get(0), Some(first.sol_amount as f64 / LAMPORTS_PER_SOL));
+        let user = launch.creator.to_string();
+        assert_eq!(frame.column("user").unwrap().str().unwrap().get(0), Some(user.as_str()));
+        // tx_index inconnu : valeur nulle, pas zéro
+        assert_eq!(frame.column("tx_index").unwrap().null_count(), trades.len());
+    }
+}
@@ -0,0 +1,5 @@
+use crate::platforms::pumpfun::accounts::TOKEN_DECIMALS;
+
+// Conversions pour l'affichage et les exports : lamports → SOL, unités de base → tokens entiers
+pub const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+pub const TOKEN_UNIT: f64 = 10u64.pow(TOKEN_DECIMALS) as f64;
@@ -1,28 +1,50 @@
 use solana_client::client_error::ClientError;
 use solana_sdk::pubkey::Pubkey;
+use polars::error::PolarsError;
 use thiserror::Error;
 use crate::domain::decoder::error::DecodeError;
+use crate::platforms::pumpfun::curve::QuoteError;
 
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("The address you gave is {0} bytes long. Solana address is always 32 bytes long")]
     WrongSizeToken(usize),
+    #[error("`{0}` is not a valid base58 Solana address")]
+    InvalidAddress(String),
+    #[error("No RPC endpoint configured. Pass --rpc-url or set RPC_URL")]
+    MissingRpcUrl,
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
     #[error("The token you want to analyze does not exist. Address used: `{0}`")]
     TokenNotFound(Pubkey),
     #[error(
-        "The address you entered is not owned by TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA. Owner: {0}"
+        "The address you entered is not a mint of the SPL Token or Token-2022 program. Owner: {0}"
     )]
     NotAToken(Pubkey),
+    #[error("No pump.fun bonding curve found for `{0}`")]
+    BondingCurveNotFound(Pubkey),
     #[error("The token you want to analyze doesn't have any transactions.")]
     NoTransactionRecorded,
+    #[error("Transaction `{0}` is not available on the RPC node")]
+    TransactionUnavailable(String),
     #[error(
-        "The token you want to analyze has more than 1000 transactions. {transactions_fetched} Transactions fetched"
+        "The token you want to analyze has more than {limit} transactions. {transactions_fetched} Transactions fetched"
     )]
-    TooManyTransactions { transactions_fetched: usize },
+    TooManyTransactions { limit: usize, transactions_fetched: usize },
     #[error(transparent)]
     Decode(#[from] DecodeError),
     #[error(transparent)]
+    Quote(#[from] QuoteError),
+    #[error(transparent)]
     Upstream(#[from] ClientError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("DataFrame error: {0}")]
+    Frame(#[from] PolarsError),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Transaction cache error: {0}")]
+    Cache(#[from] rusqlite::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,316 @@
+use crate::infra::rpc_backend::RpcBackend;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClientConfig};
+use solana_client::rpc_config::{RpcBlockConfig, RpcProgramAccountsConfig, RpcTransactionConfig};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_transaction_status_client_types::{EncodedConfirmedTransactionWithStatusMeta, UiConfirmedBlock};
+use solana_rpc_client::http_sender::HttpSender;
+use solana_sdk::hash::hashv;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tokio::time::Instant;
+
+// Les fixtures sont lues sur disque : pas de fournisseur à ménager
+const REPLAY_CONCURRENCY: usize = 16;
+
+// Répertoire de fixtures : un fichier JSON par couple (méthode, paramètres)
+#[derive(Debug, Clone)]
+pub struct FixtureStore {
+    dir: PathBuf,
+}
+
+impl FixtureStore {
+    pub fn open(dir: &Path) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Self { dir: dir.to_path_buf() })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    // serde_json trie les clés des objets : la sérialisation des paramètres est canonique
+    fn path_for(&self, request: RpcRequest, params: &Value) -> PathBuf {
+        let method = request.to_string();
+        let digest = hashv(&[method.as_bytes(), params.to_string().as_bytes()]);
+        self.dir.join(format!("{method}-{}.json", &digest.to_string()[..16]))
+    }
+
+    pub fn record(&self, request: RpcRequest, params: &Value, response: &Value) -> std::io::Result<()> {
+        let fixture = json!({
+            "method": request.to_string(),
+            "params": params,
+            "response": response,
+        });
+        let body = serde_json::to_vec_pretty(&fixture).map_err(std::io::Error::other)?;
+        std::fs::write(self.path_for(request, params), body)
+    }
+
+    pub fn lookup(&self, request: RpcRequest, params: &Value) -> Option<Value> {
+        let path = self.path_for(request, params);
+        let body = std::fs::read(&path).ok()?;
+        let mut fixture: Value = match serde_json::from_slice(&body) {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), "fixture illisible: {e}");
+                return None;
+            }
+        };
+        Some(fixture["response"].take())
+    }
+}
+
+// Transport HTTP qui enregistre chaque réponse réussie dans le répertoire de fixtures
+pub struct RecordingSender {
+    inner: HttpSender,
+    store: Arc<FixtureStore>,
+}
+
+impl RecordingSender {
+    pub fn new(url: String, store: Arc<FixtureStore>) -> Self {
+        Self {
+            inner: HttpSender::new(url),
+            store,
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for RecordingSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let response = self.inner.send(request, params.clone()).await?;
+        if let Err(e) = self.store.record(request, &params, &response) {
+            tracing::warn!(%request, "enregistrement de la fixture impossible: {e}");
+        }
+        Ok(response)
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.inner.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.inner.url()
+    }
+}
+
+// Transport hors-ligne qui rejoue les réponses enregistrées
+pub struct ReplaySender {
+    store: Arc<FixtureStore>,
+    stats: RwLock<RpcTransportStats>,
+}
+
+impl ReplaySender {
+    pub fn new(store: Arc<FixtureStore>) -> Self {
+        Self {
+            store,
+            stats: RwLock::new(RpcTransportStats::default()),
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for ReplaySender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let started = Instant::now();
+        let response = self.store.lookup(request, &params);
+        {
+            let mut stats = self.stats.write().unwrap();
+            stats.request_count += 1;
+            stats.elapsed_time += started.elapsed();
+        }
+        response.ok_or_else(|| {
+            ClientError::from(ClientErrorKind::Custom(format!(
+                "no recorded fixture for {request} {params}"
+            )))
+        })
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.stats.read().unwrap().clone()
+    }
+
+    fn url(&self) -> String {
+        format!("replay://{}", self.store.dir().display())
+    }
+}
+
+// Backend hors-ligne : même chemin de sérialisation que le client HTTP, réponses lues sur disque
+pub struct ReplayRpc {
+    client: RpcClient,
+}
+
+impl ReplayRpc {
+    pub fn new(store: Arc<FixtureStore>, commitment: CommitmentConfig) -> Self {
+        Self {
+            client: RpcClient::new_sender(ReplaySender::new(store), RpcClientConfig::with_commitment(commitment)),
+        }
+    }
+}
+
+#[async_trait]
+impl RpcBackend for ReplayRpc {
+    fn commitment(&self) -> CommitmentConfig {
+        self.client.commitment()
+    }
+
+    fn max_concurrent(&self) -> usize {
+        REPLAY_CONCURRENCY
+    }
+
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        RpcBackend::get_multiple_accounts(&self.client, addresses).await
+    }
+
+    async fn get_signatures_for_address_with_config(
+        &self,
+        address: &Pubkey,
+        config: GetConfirmedSignaturesForAddress2Config,
+    ) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        RpcBackend::get_signatures_for_address_with_config(&self.client, address, config).await
+    }
+
+    async fn get_transaction(
+        &self,
+        signature: &Signature,
+        config: RpcTransactionConfig,
+    ) -> ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+        RpcBackend::get_transaction(&self.client, signature, config).await
+    }
+
+    async fn get_slot(&self) -> ClientResult<u64> {
+        RpcBackend::get_slot(&self.client).await
+    }
+
+    async fn get_block(&self, slot: u64, config: RpcBlockConfig) -> ClientResult<UiConfirmedBlock> {
+        RpcBackend::get_block(&self.client, slot, config).await
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> ClientResult<Vec<(Pubkey, Account)>> {
+        RpcBackend::get_program_accounts(&self.client, program, config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::decoder::event::{DecodedEvent, decode_transaction, order_events};
+    use crate::domain::history::HistoryLimit;
+    use crate::domain::token_stats::TokenStats;
+    use crate::infra::mock_rpc::{MockRpcServer, Reply};
+    use crate::infra::retry::RetryPolicy;
+    use crate::infra::solana_rpc::{fetch_signature_history, retrieve_transactions};
+    use crate::platforms::pumpfun::events::TradeEnvelope;
+    use crate::platforms::pumpfun::pumpfun::PumpFun;
+    use crate::platforms::pumpfun::sample::SampleLaunch;
+    use solana_transaction_status_client_types::UiTransactionEncoding;
+    use std::collections::HashMap;
+
+    // Lancement synthétique enregistré par `RecordingSender` depuis un nœud local (voir `record_sample_launch`)
+    const SAMPLE_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/pumpfun_sample");
+
+    fn tx_config() -> RpcTransactionConfig {
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Json),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        }
+    }
+
+    // Même chemin que le scan : historique, transactions, décodage, ordre chronologique
+    async fn load_trades(rpc: &dyn RpcBackend, mint: &Pubkey) -> Vec<TradeEnvelope> {
+        let history = fetch_signature_history(rpc, mint, &HistoryLimit::default()).await.unwrap();
+        let signatures = history.signatures.iter().rev().map(|s| s.signature.clone()).collect();
+        let fetched = retrieve_transactions(rpc, None, signatures, tx_config(), &RetryPolicy::default()).await;
+        assert!(fetched.is_complete());
+
+        let mut trades = Vec::new();
+        for tx in &fetched.transactions {
+            for event in decode_transaction(&PumpFun, tx).unwrap() {
+                if let DecodedEvent::Trade(trade) = event.split() {
+                    trades.push(trade);
+                }
+            }
+        }
+        order_events(&mut trades);
+        trades
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_launch() {
+        let launch = SampleLaunch::new();
+        let store = Arc::new(FixtureStore::open(Path::new(SAMPLE_FIXTURES)).unwrap());
+        let rpc = ReplayRpc::new(store, CommitmentConfig::confirmed());
+
+        let trades = load_trades(&rpc, &launch.mint).await;
+        let stats = TokenStats::new(&trades);
+
+        // Montants tels qu'enregistrés : un changement du décodeur doit les retrouver à l'identique
+        let [sniper, early, late] = launch.buyers;
+        let expected = vec![
+            (launch.creator, true, 495_049_504, 17_418_831_135_929),
+            (sniper, true, 990_099_009, 33_194_376_351_118),
+            (early, true, 495_049_504, 15_826_420_907_732),
+            (sniper, false, 518_767_964, 16_597_188_175_559),
+            (late, true, 1_980_198_019, 60_584_806_715_769),
+        ];
+        let decoded: Vec<(Pubkey, bool, u64, u64)> = trades
+            .iter()
+            .map(|t| (t.user, t.is_buy, t.sol_amount, t.token_amount))
+            .collect();
+        assert_eq!(decoded, expected);
+        assert_eq!(trades[0].user, launch.creator);
+        assert_eq!(trades[0].signature, launch.transactions[0].signature.to_string());
+
+        assert_eq!(stats.total_trades, 5);
+        assert_eq!(stats.buy_count, 4);
+        assert_eq!(stats.sell_count, 1);
+        assert_eq!(stats.makers_count, 4);
+        assert_eq!(stats.buyers_count, 4);
+        assert_eq!(stats.sellers_count, 1);
+        assert!((stats.total_volume_sol - 4.479_164_000).abs() < 1e-9);
+        assert!((stats.buy_volume_sol - 3.960_396_036).abs() < 1e-9);
+    }
+
+    // Régénère les fixtures : `cargo test record_sample_launch -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn record_sample_launch() {
+        let launch = SampleLaunch::new();
+        let statuses = serde_json::to_value(launch.statuses()).unwrap();
+        let transactions: HashMap<String, Value> = launch
+            .transactions
+            .iter()
+            .map(|tx| (tx.signature.to_string(), serde_json::to_value(tx.encoded()).unwrap()))
+            .collect();
+        let server = MockRpcServer::start(move |method, params| match method {
+            "getSignaturesForAddress" => Reply::Result(statuses.clone()),
+            "getTransaction" => {
+                let signature = params[0].as_str().unwrap_or_default();
+                Reply::Result(transactions.get(signature).cloned().unwrap_or(Value::Null))
+            }
+            _ => Reply::Result(Value::Null),
+        })
+        .await;
+
+        let _ = std::fs::remove_dir_all(SAMPLE_FIXTURES);
+        let store = Arc::new(FixtureStore::open(Path::new(SAMPLE_FIXTURES)).unwrap());
+        let client = RpcClient::new_sender(
+            RecordingSender::new(server.url(), store),
+            RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+        );
+        assert_eq!(load_trades(&client, &launch.mint).await.len(), 5);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::error::{self, Result};
+use polars::prelude::*;
+use std::fs::File;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    Csv,
+    Parquet,
+    // Un objet JSON par ligne
+    Ndjson,
+}
+
+impl FrameFormat {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => Ok(FrameFormat::Csv),
+            Some("parquet") => Ok(FrameFormat::Parquet),
+            Some("ndjson" | "jsonl") => Ok(FrameFormat::Ndjson),
+            _ => Err(error::Error::InvalidArgument(format!(
+                "cannot infer the output format of `{}` (expected .csv, .parquet or .ndjson)",
+                path.display()
+            ))),
+        }
+    }
+}
+
+// Écrit un DataFrame au format déduit de l'extension du fichier
+pub fn write_frame(frame: &mut DataFrame, path: &Path) -> Result<()> {
+    let format = FrameFormat::from_path(path)?;
+    let file = File::create(path)?;
+    match format {
+        FrameFormat::Csv => CsvWriter::new(file).finish(frame)?,
+        FrameFormat::Parquet => {
+            ParquetWriter::new(file).finish(frame)?;
+        }
+        FrameFormat::Ndjson => JsonWriter::new(file).with_json_format(JsonFormat::JsonLines).finish(frame)?,
+    }
+    tracing::info!(path = %path.display(), rows = frame.height(), "💾 fichier écrit");
+    Ok(())
+}
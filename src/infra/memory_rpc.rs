@@ -0,0 +1,145 @@
+use crate::infra::rpc_backend::RpcBackend;
+use async_trait::async_trait;
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::{RpcBlockConfig, RpcProgramAccountsConfig, RpcTransactionConfig};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_transaction_status_client_types::{EncodedConfirmedTransactionWithStatusMeta, UiConfirmedBlock};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+// Même taille de page par défaut que les nœuds
+const DEFAULT_SIGNATURE_LIMIT: usize = 1000;
+
+// Faux RPC en mémoire : on y dépose comptes, signatures, transactions et blocs à la main
+#[derive(Default)]
+pub struct MemoryRpc {
+    commitment: CommitmentConfig,
+    slot: u64,
+    accounts: HashMap<Pubkey, Account>,
+    // Par adresse, de la plus récente à la plus ancienne comme le vrai RPC
+    signatures: HashMap<Pubkey, Vec<RpcConfirmedTransactionStatusWithSignature>>,
+    transactions: HashMap<String, EncodedConfirmedTransactionWithStatusMeta>,
+    blocks: HashMap<u64, UiConfirmedBlock>,
+}
+
+impl MemoryRpc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_account(mut self, address: Pubkey, account: Account) -> Self {
+        self.accounts.insert(address, account);
+        self
+    }
+
+    pub fn with_signatures(
+        mut self,
+        address: Pubkey,
+        statuses: impl IntoIterator<Item = RpcConfirmedTransactionStatusWithSignature>,
+    ) -> Self {
+        let list = self.signatures.entry(address).or_default();
+        list.extend(statuses);
+        list.sort_by_key(|status| Reverse(status.slot));
+        self
+    }
+
+    pub fn with_transaction(mut self, signature: &str, tx: EncodedConfirmedTransactionWithStatusMeta) -> Self {
+        self.slot = self.slot.max(tx.slot);
+        self.transactions.insert(signature.to_string(), tx);
+        self
+    }
+
+    pub fn with_block(mut self, slot: u64, block: UiConfirmedBlock) -> Self {
+        self.slot = self.slot.max(slot);
+        self.blocks.insert(slot, block);
+        self
+    }
+}
+
+#[async_trait]
+impl RpcBackend for MemoryRpc {
+    fn commitment(&self) -> CommitmentConfig {
+        self.commitment
+    }
+
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        Ok(addresses.iter().map(|a| self.accounts.get(a).cloned()).collect())
+    }
+
+    // Reproduit la pagination `before` / `until` / `limit` de getSignaturesForAddress
+    async fn get_signatures_for_address_with_config(
+        &self,
+        address: &Pubkey,
+        config: GetConfirmedSignaturesForAddress2Config,
+    ) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        let Some(list) = self.signatures.get(address) else {
+            return Ok(Vec::new());
+        };
+        let before = config.before.map(|s| s.to_string());
+        let until = config.until.map(|s| s.to_string());
+
+        let start = match &before {
+            Some(before) => match list.iter().position(|s| &s.signature == before) {
+                Some(index) => index + 1,
+                None => return Ok(Vec::new()),
+            },
+            None => 0,
+        };
+        Ok(list[start..]
+            .iter()
+            .take_while(|s| until.as_ref() != Some(&s.signature))
+            .take(config.limit.unwrap_or(DEFAULT_SIGNATURE_LIMIT))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_transaction(
+        &self,
+        signature: &Signature,
+        _config: RpcTransactionConfig,
+    ) -> ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+        // Le type n'implémente pas Clone : on le reconstruit champ par champ
+        Ok(self.transactions.get(&signature.to_string()).map(|tx| EncodedConfirmedTransactionWithStatusMeta {
+            slot: tx.slot,
+            transaction: tx.transaction.clone(),
+            block_time: tx.block_time,
+        }))
+    }
+
+    async fn get_slot(&self) -> ClientResult<u64> {
+        Ok(self.slot)
+    }
+
+    async fn get_block(&self, slot: u64, _config: RpcBlockConfig) -> ClientResult<UiConfirmedBlock> {
+        self.blocks.get(&slot).cloned().ok_or_else(|| {
+            ClientError::from(ClientErrorKind::Custom(format!("block {slot} not available")))
+        })
+    }
+
+    // Applique les filtres `dataSize` / `memcmp` comme le nœud
+    async fn get_program_accounts(
+        &self,
+        program: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> ClientResult<Vec<(Pubkey, Account)>> {
+        let filters = config.filters.unwrap_or_default();
+        Ok(self
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.owner == *program)
+            .filter(|(_, account)| {
+                filters.iter().all(|filter| match filter {
+                    RpcFilterType::DataSize(size) => account.data.len() as u64 == *size,
+                    RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&account.data),
+                    RpcFilterType::TokenAccountState => true,
+                })
+            })
+            .map(|(address, account)| (*address, account.clone()))
+            .collect())
+    }
+}
@@ -0,0 +1,101 @@
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+const SUBSCRIPTION_ID: u64 = 1;
+
+// Résultat d'un logsNotification : `{"context": {"slot"}, "value": {"signature", "err", "logs"}}`
+pub fn logs_notification(slot: u64, signature: &str, logs: &[String]) -> Value {
+    json!({
+        "context": { "slot": slot },
+        "value": { "signature": signature, "err": null, "logs": logs },
+    })
+}
+
+// Nœud websocket local : chaque connexion rejoue une session de notifications.
+// Toutes les sessions sauf la dernière se terminent par une coupure du serveur.
+pub struct MockPubsubServer {
+    url: String,
+    connections: Arc<AtomicUsize>,
+    task: JoinHandle<()>,
+}
+
+impl MockPubsubServer {
+    pub async fn start(sessions: Vec<Vec<Value>>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind mock pubsub server");
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let task = tokio::spawn({
+            let connections = connections.clone();
+            async move {
+                let mut sessions = sessions.into_iter();
+                while let Ok((stream, _)) = listener.accept().await {
+                    connections.fetch_add(1, Ordering::SeqCst);
+                    let notifications = sessions.next().unwrap_or_default();
+                    let last = sessions.len() == 0;
+                    tokio::spawn(serve(stream, notifications, last));
+                }
+            }
+        });
+        Self { url, connections, task }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for MockPubsubServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve(stream: TcpStream, notifications: Vec<Value>, keep_open: bool) {
+    let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await else {
+        return;
+    };
+    while let Some(Ok(message)) = ws.next().await {
+        let Message::Text(text) = message else {
+            continue;
+        };
+        let request: Value = serde_json::from_str(&text).unwrap_or_default();
+        match request["method"].as_str() {
+            Some("logsSubscribe") => {
+                let reply = json!({ "jsonrpc": "2.0", "id": request["id"], "result": SUBSCRIPTION_ID });
+                if ws.send(Message::Text(reply.to_string())).await.is_err() {
+                    return;
+                }
+                for result in &notifications {
+                    let notification = json!({
+                        "jsonrpc": "2.0",
+                        "method": "logsNotification",
+                        "params": { "result": result, "subscription": SUBSCRIPTION_ID },
+                    });
+                    if ws.send(Message::Text(notification.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+                if !keep_open {
+                    let _ = ws.close(None).await;
+                    return;
+                }
+            }
+            Some(_) => {
+                let reply = json!({ "jsonrpc": "2.0", "id": request["id"], "result": true });
+                if ws.send(Message::Text(reply.to_string())).await.is_err() {
+                    return;
+                }
+            }
+            None => {}
+        }
+    }
+}
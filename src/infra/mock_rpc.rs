@@ -0,0 +1,117 @@
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+
+// Code renvoyé par un nœud en retard : transitoire pour `classify_error`
+pub const NODE_BEHIND: i64 = -32005;
+
+// Réponse du faux nœud à une requête JSON-RPC
+#[derive(Debug, Clone)]
+pub enum Reply {
+    Result(Value),
+    Error(i64),
+    Slow(Duration, Box<Reply>),
+}
+
+type Handler = dyn Fn(&str, &Value) -> Reply + Send + Sync;
+
+// Nœud JSON-RPC local pour les tests : HTTP/1.1 minimal, réponses choisies par méthode
+pub struct MockRpcServer {
+    url: String,
+    calls: Arc<Mutex<HashMap<String, usize>>>,
+    task: JoinHandle<()>,
+}
+
+impl MockRpcServer {
+    pub async fn start(handler: impl Fn(&str, &Value) -> Reply + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind mock RPC server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let calls = Arc::new(Mutex::new(HashMap::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+        let task = tokio::spawn({
+            let calls = calls.clone();
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(serve(stream, handler.clone(), calls.clone()));
+                }
+            }
+        });
+        Self { url, calls, task }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    pub fn calls(&self, method: &str) -> usize {
+        self.calls.lock().unwrap().get(method).copied().unwrap_or(0)
+    }
+}
+
+impl Drop for MockRpcServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// Une connexion keep-alive : on lit les requêtes tant que le client la garde ouverte
+async fn serve(stream: TcpStream, handler: Arc<Handler>, calls: Arc<Mutex<HashMap<String, usize>>>) {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    loop {
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            match reader.read_line(&mut line).await {
+                Ok(0) | Err(_) => return,
+                Ok(_) => {}
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                length = value.trim().parse().unwrap_or(0);
+            }
+        }
+        let mut body = vec![0; length];
+        if reader.read_exact(&mut body).await.is_err() {
+            return;
+        }
+        let request: Value = serde_json::from_slice(&body).unwrap_or_default();
+        let method = request["method"].as_str().unwrap_or_default().to_string();
+        *calls.lock().unwrap().entry(method.clone()).or_default() += 1;
+
+        let mut reply = handler(&method, &request["params"]);
+        let response = loop {
+            match reply {
+                Reply::Slow(delay, inner) => {
+                    tokio::time::sleep(delay).await;
+                    reply = *inner;
+                }
+                Reply::Result(result) => break json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+                Reply::Error(code) => {
+                    break json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "error": { "code": code, "message": "mock error" },
+                    });
+                }
+            }
+        };
+        let body = response.to_string();
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        );
+        if write.write_all(head.as_bytes()).await.is_err() || write.write_all(body.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
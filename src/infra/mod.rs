@@ -1 +1,15 @@
+pub mod fixtures;
+pub mod frame_writer;
+#[cfg(test)]
+pub mod memory_rpc;
+#[cfg(test)]
+pub mod mock_pubsub;
+#[cfg(test)]
+pub mod mock_rpc;
+pub mod rate_limit;
+pub mod retry;
+pub mod rpc_backend;
+pub mod rpc_pool;
 pub mod solana_rpc;
+pub mod throttled_rpc;
+pub mod tx_cache;
@@ -0,0 +1,268 @@
+use solana_client::rpc_request::RpcRequest;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::time::{Duration, Instant};
+
+// Délai sans 429 avant de remonter progressivement le débit
+const RECOVERY_DELAY: Duration = Duration::from_secs(10);
+const MIN_CREDITS_PER_SECOND: f64 = 0.5;
+// Une surcharge renvoie un 429 par requête en vol : on ne divise le débit qu'une fois par fenêtre
+const BACKOFF_WINDOW: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+pub struct ProviderProfile {
+    pub name: String,
+    pub credits_per_second: f64,
+    pub max_concurrent: usize,
+    pub weights: HashMap<RpcRequest, u32>,
+}
+
+impl ProviderProfile {
+    pub fn new(name: &str, credits_per_second: f64, max_concurrent: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            credits_per_second,
+            max_concurrent,
+            weights: HashMap::new(),
+        }
+    }
+
+    pub fn with_weight(mut self, method: RpcRequest, credits: u32) -> Self {
+        self.weights.insert(method, credits);
+        self
+    }
+
+    pub fn weight(&self, method: RpcRequest) -> u32 {
+        self.weights.get(&method).copied().unwrap_or(1)
+    }
+
+    // Les valeurs reprennent les offres d'entrée de gamme, à ajuster avec --rps / --rpc-weight
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::new("default", 13.0, 10)),
+            "public" => Some(Self::new("public", 4.0, 4).with_weight(RpcRequest::GetProgramAccounts, 4)),
+            "helius" => Some(
+                Self::new("helius", 50.0, 20)
+                    .with_weight(RpcRequest::GetProgramAccounts, 10)
+                    .with_weight(RpcRequest::GetBlock, 10),
+            ),
+            "quicknode" => Some(
+                Self::new("quicknode", 25.0, 15)
+                    .with_weight(RpcRequest::GetTransaction, 2)
+                    .with_weight(RpcRequest::GetSignaturesForAddress, 2)
+                    .with_weight(RpcRequest::GetProgramAccounts, 10)
+                    .with_weight(RpcRequest::GetBlock, 10),
+            ),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_method(method: &str) -> Option<RpcRequest> {
+    match method {
+        "getAccountInfo" => Some(RpcRequest::GetAccountInfo),
+        "getMultipleAccounts" => Some(RpcRequest::GetMultipleAccounts),
+        "getSignaturesForAddress" => Some(RpcRequest::GetSignaturesForAddress),
+        "getTransaction" => Some(RpcRequest::GetTransaction),
+        "getSlot" => Some(RpcRequest::GetSlot),
+        "getBlock" => Some(RpcRequest::GetBlock),
+        "getProgramAccounts" => Some(RpcRequest::GetProgramAccounts),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    rate: f64,
+    available: f64,
+    last_refill: Instant,
+    last_throttle: Option<Instant>,
+    last_backoff: Option<Instant>,
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    profile: ProviderProfile,
+    capacity: f64,
+    bucket: Mutex<Bucket>,
+    permits: Semaphore,
+}
+
+impl RateLimiter {
+    pub fn new(profile: ProviderProfile) -> Self {
+        let max_weight = profile.weights.values().copied().max().unwrap_or(1) as f64;
+        let capacity = profile.credits_per_second.max(max_weight);
+        Self {
+            capacity,
+            bucket: Mutex::new(Bucket {
+                rate: profile.credits_per_second,
+                available: capacity,
+                last_refill: Instant::now(),
+                last_throttle: None,
+                last_backoff: None,
+            }),
+            permits: Semaphore::new(profile.max_concurrent.max(1)),
+            profile,
+        }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.profile.max_concurrent.max(1)
+    }
+
+    // Attend une place de concurrence puis les crédits de la méthode
+    pub async fn acquire(&self, method: RpcRequest) -> SemaphorePermit<'_> {
+        let permit = self.permits.acquire().await.expect("rate limiter semaphore closed");
+        let cost = self.profile.weight(method) as f64;
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap();
+                let now = Instant::now();
+                let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+                bucket.available = (bucket.available + elapsed * bucket.rate).min(self.capacity);
+                bucket.last_refill = now;
+                if bucket.available >= cost {
+                    bucket.available -= cost;
+                    None
+                } else {
+                    Some(Duration::from_secs_f64((cost - bucket.available) / bucket.rate))
+                }
+            };
+            match wait {
+                None => return permit,
+                Some(delay) => tokio::time::sleep(delay).await,
+            }
+        }
+    }
+
+    // 429 reçu : on vide le seau et on divise le débit par deux, au plus une fois par BACKOFF_WINDOW
+    pub fn on_rate_limited(&self) {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        bucket.available = 0.0;
+        bucket.last_throttle = Some(now);
+        if bucket.last_backoff.is_some_and(|t| now.duration_since(t) < BACKOFF_WINDOW) {
+            return;
+        }
+        bucket.last_backoff = Some(now);
+        bucket.rate = (bucket.rate / 2.0).max(MIN_CREDITS_PER_SECOND);
+        tracing::warn!(provider = %self.profile.name, rate = bucket.rate, "🐢 429 reçu, débit réduit");
+    }
+
+    // Remontée additive vers le débit nominal une fois le fournisseur calmé
+    pub fn on_success(&self) {
+        let mut bucket = self.bucket.lock().unwrap();
+        if bucket.rate >= self.profile.credits_per_second {
+            return;
+        }
+        let calm = bucket
+            .last_throttle
+            .is_none_or(|t| t.elapsed() >= RECOVERY_DELAY);
+        if calm {
+            bucket.rate = (bucket.rate + self.profile.credits_per_second * 0.05)
+                .min(self.profile.credits_per_second);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(limiter: &RateLimiter) -> f64 {
+        limiter.bucket.lock().unwrap().rate
+    }
+
+    // Temps écoulé pour obtenir les crédits de `method`, permis rendu aussitôt
+    async fn wait_for(limiter: &RateLimiter, method: RpcRequest) -> Duration {
+        let started = Instant::now();
+        drop(limiter.acquire(method).await);
+        started.elapsed()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_full_bucket_serves_a_burst_then_paces_requests() {
+        let limiter = RateLimiter::new(ProviderProfile::new("test", 10.0, 10));
+
+        for _ in 0..10 {
+            assert_eq!(wait_for(&limiter, RpcRequest::GetAccountInfo).await, Duration::ZERO);
+        }
+        assert_eq!(wait_for(&limiter, RpcRequest::GetAccountInfo).await, Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn heavy_methods_spend_their_weight() {
+        let profile = ProviderProfile::new("test", 4.0, 4).with_weight(RpcRequest::GetProgramAccounts, 4);
+        let limiter = RateLimiter::new(profile);
+
+        assert_eq!(wait_for(&limiter, RpcRequest::GetProgramAccounts).await, Duration::ZERO);
+        assert_eq!(wait_for(&limiter, RpcRequest::GetAccountInfo).await, Duration::from_millis(250));
+        assert_eq!(wait_for(&limiter, RpcRequest::GetProgramAccounts).await, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn the_bucket_holds_at_least_the_heaviest_method() {
+        let profile = ProviderProfile::new("test", 2.0, 4).with_weight(RpcRequest::GetBlock, 10);
+        let limiter = RateLimiter::new(profile);
+
+        assert_eq!(limiter.capacity, 10.0);
+        assert_eq!(limiter.profile.weight(RpcRequest::GetTransaction), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_burst_of_429s_halves_the_rate_once() {
+        let limiter = RateLimiter::new(ProviderProfile::new("test", 16.0, 10));
+
+        for _ in 0..10 {
+            limiter.on_rate_limited();
+        }
+        assert_eq!(rate(&limiter), 8.0);
+
+        tokio::time::advance(BACKOFF_WINDOW).await;
+        limiter.on_rate_limited();
+        assert_eq!(rate(&limiter), 4.0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_rate_never_drops_below_the_floor() {
+        let limiter = RateLimiter::new(ProviderProfile::new("test", 1.0, 1));
+
+        for _ in 0..3 {
+            limiter.on_rate_limited();
+            tokio::time::advance(BACKOFF_WINDOW).await;
+        }
+        assert_eq!(rate(&limiter), MIN_CREDITS_PER_SECOND);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_rate_recovers_additively_once_calm() {
+        let limiter = RateLimiter::new(ProviderProfile::new("test", 20.0, 10));
+        limiter.on_rate_limited();
+
+        limiter.on_success();
+        assert_eq!(rate(&limiter), 10.0);
+
+        tokio::time::advance(RECOVERY_DELAY).await;
+        limiter.on_success();
+        assert_eq!(rate(&limiter), 11.0);
+
+        for _ in 0..20 {
+            limiter.on_success();
+        }
+        assert_eq!(rate(&limiter), 20.0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_429_restarts_the_calm_period() {
+        let limiter = RateLimiter::new(ProviderProfile::new("test", 20.0, 10));
+        limiter.on_rate_limited();
+        tokio::time::advance(Duration::from_secs(1)).await;
+        // Toujours dans la fenêtre : le débit ne baisse pas mais le calme repart de zéro
+        limiter.on_rate_limited();
+
+        tokio::time::advance(RECOVERY_DELAY - Duration::from_secs(1)).await;
+        limiter.on_success();
+        assert_eq!(rate(&limiter), 10.0);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::domain::fetch::FailureKind;
+use rand::Rng;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_request::RpcError;
+use tokio::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 4,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    // Backoff exponentiel avec "full jitter" : uniforme dans [0, base * 2^attempt]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .base_delay
+            .saturating_mul(1u32 << attempt.min(16))
+            .min(self.max_delay);
+        let jitter_ms = rand::rng().random_range(0..=ceiling.as_millis() as u64);
+        Duration::from_millis(jitter_ms)
+    }
+}
+
+pub fn classify_error(e: &ClientError) -> FailureKind {
+    match e.kind() {
+        ClientErrorKind::Reqwest(err) => {
+            if err.is_timeout() {
+                FailureKind::Timeout
+            } else if let Some(status) = err.status() {
+                if status.as_u16() == 429 {
+                    FailureKind::RateLimited
+                } else if status.is_server_error() {
+                    FailureKind::ServerError
+                } else {
+                    FailureKind::Rpc
+                }
+            } else if err.is_connect() || err.is_request() {
+                FailureKind::ServerError
+            } else {
+                FailureKind::Rpc
+            }
+        }
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => match code {
+            429 | -32429 => FailureKind::RateLimited,
+            // -32005 : nœud en retard, -32004 : bloc pas encore disponible
+            -32005 | -32004 => FailureKind::ServerError,
+            _ => FailureKind::Rpc,
+        },
+        ClientErrorKind::Io(err) if err.kind() == std::io::ErrorKind::TimedOut => FailureKind::Timeout,
+        _ => FailureKind::Rpc,
+    }
+}
+
+pub fn is_retryable(kind: FailureKind) -> bool {
+    matches!(
+        kind,
+        FailureKind::RateLimited | FailureKind::Timeout | FailureKind::ServerError
+    )
+}
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use serde_json::json;
+use solana_client::client_error::Result as ClientResult;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::{RpcBlockConfig, RpcProgramAccountsConfig, RpcTransactionConfig};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_transaction_status_client_types::{EncodedConfirmedTransactionWithStatusMeta, UiConfirmedBlock};
+
+// Opérations RPC dont les services ont besoin, indépendamment du transport
+#[async_trait]
+pub trait RpcBackend: Send + Sync {
+    fn commitment(&self) -> CommitmentConfig;
+
+    // Nombre de requêtes que l'appelant peut garder en vol
+    fn max_concurrent(&self) -> usize {
+        1
+    }
+
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> ClientResult<Vec<Option<Account>>>;
+
+    async fn get_signatures_for_address_with_config(
+        &self,
+        address: &Pubkey,
+        config: GetConfirmedSignaturesForAddress2Config,
+    ) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>>;
+
+    // `Ok(None)` : le nœud ne connaît pas (encore) la transaction
+    async fn get_transaction(
+        &self,
+        signature: &Signature,
+        config: RpcTransactionConfig,
+    ) -> ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>>;
+
+    async fn get_slot(&self) -> ClientResult<u64>;
+
+    async fn get_block(&self, slot: u64, config: RpcBlockConfig) -> ClientResult<UiConfirmedBlock>;
+
+    async fn get_program_accounts(
+        &self,
+        program: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> ClientResult<Vec<(Pubkey, Account)>>;
+}
+
+#[async_trait]
+impl RpcBackend for RpcClient {
+    fn commitment(&self) -> CommitmentConfig {
+        RpcClient::commitment(self)
+    }
+
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        RpcClient::get_multiple_accounts(self, addresses).await
+    }
+
+    async fn get_signatures_for_address_with_config(
+        &self,
+        address: &Pubkey,
+        config: GetConfirmedSignaturesForAddress2Config,
+    ) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        RpcClient::get_signatures_for_address_with_config(self, address, config).await
+    }
+
+    // `send` plutôt que `get_transaction_with_config` pour distinguer un `null` d'une erreur
+    async fn get_transaction(
+        &self,
+        signature: &Signature,
+        config: RpcTransactionConfig,
+    ) -> ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+        self.send(RpcRequest::GetTransaction, json!([signature.to_string(), config]))
+            .await
+    }
+
+    async fn get_slot(&self) -> ClientResult<u64> {
+        RpcClient::get_slot(self).await
+    }
+
+    async fn get_block(&self, slot: u64, config: RpcBlockConfig) -> ClientResult<UiConfirmedBlock> {
+        RpcClient::get_block_with_config(self, slot, config).await
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> ClientResult<Vec<(Pubkey, Account)>> {
+        RpcClient::get_program_accounts_with_config(self, program, config).await
+    }
+}
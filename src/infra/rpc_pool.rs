@@ -0,0 +1,479 @@
+use crate::infra::retry::{classify_error, is_retryable};
+use crate::infra::rpc_backend::RpcBackend;
+use crate::infra::throttled_rpc::ThrottledRpc;
+use async_trait::async_trait;
+use futures::future::join_all;
+use solana_client::client_error::{ClientError, Result as ClientResult};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::{RpcBlockConfig, RpcProgramAccountsConfig, RpcTransactionConfig};
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_transaction_status_client_types::{EncodedConfirmedTransactionWithStatusMeta, UiConfirmedBlock};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::time::{Duration, Instant};
+
+const FAILURES_BEFORE_COOLDOWN: u32 = 3;
+const COOLDOWN: Duration = Duration::from_secs(30);
+const SLOT_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+type TransactionResult = ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PoolOptions {
+    // Retard toléré (en slots) par rapport au nœud le plus avancé
+    pub max_slot_lag: u64,
+    // Relance un getTransaction lent sur un second endpoint après ce délai
+    pub hedge_after: Option<Duration>,
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            max_slot_lag: 50,
+            hedge_after: None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    down_until: Option<Instant>,
+    slot: Option<u64>,
+    lagging: bool,
+    latency_ms: Option<f64>,
+}
+
+pub struct Endpoint {
+    pub name: String,
+    rpc: Arc<ThrottledRpc>,
+    health: Mutex<Health>,
+}
+
+impl Endpoint {
+    fn new(rpc: ThrottledRpc) -> Self {
+        Self {
+            name: rpc.url(),
+            rpc: Arc::new(rpc),
+            health: Mutex::new(Health::default()),
+        }
+    }
+
+    fn is_down(&self) -> bool {
+        let health = self.health.lock().unwrap();
+        health.down_until.is_some_and(|until| Instant::now() < until)
+    }
+
+    // Tri : disponible d'abord, puis à jour en slot, puis le moins d'échecs, puis le plus rapide
+    fn rank(&self) -> (bool, bool, u32, u64) {
+        let down = self.is_down();
+        let health = self.health.lock().unwrap();
+        (
+            down,
+            health.lagging,
+            health.consecutive_failures,
+            health.latency_ms.unwrap_or(f64::MAX / 2.0) as u64,
+        )
+    }
+
+    fn record<T>(&self, result: &ClientResult<T>, latency: Duration) {
+        let mut health = self.health.lock().unwrap();
+        match result {
+            Ok(_) => {
+                let ms = latency.as_secs_f64() * 1_000.0;
+                health.latency_ms = Some(match health.latency_ms {
+                    Some(prev) => prev * 0.8 + ms * 0.2,
+                    None => ms,
+                });
+                health.consecutive_failures = 0;
+                health.down_until = None;
+            }
+            Err(e) if is_retryable(classify_error(e)) => {
+                health.consecutive_failures += 1;
+                if health.consecutive_failures >= FAILURES_BEFORE_COOLDOWN {
+                    health.down_until = Some(Instant::now() + COOLDOWN);
+                    tracing::warn!(endpoint = %self.name, "🔌 endpoint mis de côté après {} échecs", health.consecutive_failures);
+                }
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+// Plusieurs endpoints RPC derrière une seule interface, avec bascule et requêtes doublées
+pub struct RpcPool {
+    endpoints: Vec<Arc<Endpoint>>,
+    options: PoolOptions,
+    last_slot_refresh: Mutex<Option<Instant>>,
+}
+
+impl RpcPool {
+    pub fn new(endpoints: Vec<ThrottledRpc>, options: PoolOptions) -> Self {
+        Self {
+            endpoints: endpoints.into_iter().map(|rpc| Arc::new(Endpoint::new(rpc))).collect(),
+            options,
+            last_slot_refresh: Mutex::new(None),
+        }
+    }
+
+    pub async fn refresh_slots(&self) {
+        let slots = join_all(self.endpoints.iter().map(|endpoint| async move {
+            let started = Instant::now();
+            let result = endpoint.rpc.get_slot().await;
+            endpoint.record(&result, started.elapsed());
+            result.ok()
+        }))
+        .await;
+
+        let Some(highest) = slots.iter().flatten().max().copied() else {
+            return;
+        };
+        for (endpoint, slot) in self.endpoints.iter().zip(slots) {
+            let mut health = endpoint.health.lock().unwrap();
+            health.slot = slot;
+            let lagging = slot.is_none_or(|s| highest.saturating_sub(s) > self.options.max_slot_lag);
+            if lagging && !health.lagging {
+                tracing::warn!(endpoint = %endpoint.name, slot, highest, "⏳ endpoint en retard");
+            }
+            health.lagging = lagging;
+        }
+    }
+
+    async fn candidates(&self) -> Vec<Arc<Endpoint>> {
+        if self.endpoints.len() > 1 {
+            let stale = {
+                let mut last = self.last_slot_refresh.lock().unwrap();
+                let stale = last.is_none_or(|t| t.elapsed() >= SLOT_REFRESH_INTERVAL);
+                if stale {
+                    *last = Some(Instant::now());
+                }
+                stale
+            };
+            if stale {
+                self.refresh_slots().await;
+            }
+        }
+        let mut ranked: Vec<Arc<Endpoint>> = self.endpoints.clone();
+        ranked.sort_by_key(|endpoint| endpoint.rank());
+        ranked
+    }
+
+    async fn call<T, F, Fut>(endpoint: &Endpoint, op: F) -> ClientResult<T>
+    where
+        F: FnOnce(Arc<ThrottledRpc>) -> Fut,
+        Fut: Future<Output = ClientResult<T>>,
+    {
+        let started = Instant::now();
+        let result = op(endpoint.rpc.clone()).await;
+        endpoint.record(&result, started.elapsed());
+        result
+    }
+
+    // Essaie les endpoints dans l'ordre tant que l'erreur est transitoire
+    async fn with_failover<T, F, Fut>(&self, op: F) -> ClientResult<T>
+    where
+        F: Fn(Arc<ThrottledRpc>) -> Fut,
+        Fut: Future<Output = ClientResult<T>>,
+    {
+        let mut last_error = None;
+        for endpoint in self.candidates().await {
+            match Self::call(&endpoint, &op).await {
+                Ok(value) => return Ok(value),
+                Err(e) if is_retryable(classify_error(&e)) => {
+                    tracing::warn!(endpoint = %endpoint.name, "bascule vers l'endpoint suivant: {e}");
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(no_endpoint))
+    }
+}
+
+#[async_trait]
+impl RpcBackend for RpcPool {
+    fn commitment(&self) -> CommitmentConfig {
+        self.endpoints
+            .first()
+            .map(|e| e.rpc.commitment())
+            .unwrap_or_default()
+    }
+
+    fn max_concurrent(&self) -> usize {
+        self.endpoints
+            .iter()
+            .map(|e| e.rpc.limiter().max_concurrent())
+            .sum::<usize>()
+            .max(1)
+    }
+
+    async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        self.with_failover(move |rpc| async move { rpc.get_multiple_accounts(addresses).await })
+            .await
+    }
+
+    async fn get_signatures_for_address_with_config(
+        &self,
+        address: &Pubkey,
+        config: GetConfirmedSignaturesForAddress2Config,
+    ) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        let GetConfirmedSignaturesForAddress2Config { before, until, limit, commitment } = config;
+        self.with_failover(move |rpc| async move {
+            let config = GetConfirmedSignaturesForAddress2Config { before, until, limit, commitment };
+            rpc.get_signatures_for_address_with_config(address, config).await
+        })
+        .await
+    }
+
+    async fn get_slot(&self) -> ClientResult<u64> {
+        self.with_failover(move |rpc| async move { rpc.get_slot().await }).await
+    }
+
+    async fn get_block(&self, slot: u64, config: RpcBlockConfig) -> ClientResult<UiConfirmedBlock> {
+        self.with_failover(move |rpc| async move { rpc.get_block(slot, config).await })
+            .await
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> ClientResult<Vec<(Pubkey, Account)>> {
+        self.with_failover(|rpc| {
+            let config = config.clone();
+            async move { rpc.get_program_accounts(program, config).await }
+        })
+        .await
+    }
+
+    async fn get_transaction(
+        &self,
+        signature: &Signature,
+        config: RpcTransactionConfig,
+    ) -> TransactionResult {
+        let candidates = self.candidates().await;
+        if candidates.is_empty() {
+            return Err(no_endpoint());
+        }
+        let fetch = move |rpc: Arc<ThrottledRpc>| async move { rpc.get_transaction(signature, config).await };
+
+        // Un `null` peut venir d'un nœud en retard : on interroge les autres avant de conclure
+        let mut last_error = None;
+        let mut seen_null = false;
+        let mut rest: &[Arc<Endpoint>] = &candidates;
+        if let (Some(delay), [primary, secondary, others @ ..]) = (self.options.hedge_after, candidates.as_slice()) {
+            let primary_call = Self::call(primary, fetch);
+            tokio::pin!(primary_call);
+            let early = tokio::select! {
+                result = &mut primary_call => Some(result),
+                _ = tokio::time::sleep(delay) => None,
+            };
+
+            match early {
+                Some(result) => {
+                    if let Some(done) = settle(result, &mut last_error, &mut seen_null) {
+                        return done;
+                    }
+                    rest = &candidates[1..];
+                }
+                None => {
+                    tracing::debug!(%signature, endpoint = %secondary.name, "requête doublée");
+                    let secondary_call = Self::call(secondary, fetch);
+                    tokio::pin!(secondary_call);
+                    // Le premier à répondre une transaction gagne, sinon on attend l'autre
+                    let pending = tokio::select! {
+                        result = &mut primary_call => {
+                            if let Some(done) = settle(result, &mut last_error, &mut seen_null) {
+                                return done;
+                            }
+                            secondary_call.await
+                        }
+                        result = &mut secondary_call => {
+                            if let Some(done) = settle(result, &mut last_error, &mut seen_null) {
+                                return done;
+                            }
+                            primary_call.await
+                        }
+                    };
+                    if let Some(done) = settle(pending, &mut last_error, &mut seen_null) {
+                        return done;
+                    }
+                    rest = others;
+                }
+            }
+        }
+
+        for endpoint in rest {
+            if let Some(done) = settle(Self::call(endpoint, fetch).await, &mut last_error, &mut seen_null) {
+                return done;
+            }
+        }
+        match last_error {
+            Some(e) if !seen_null => Err(e),
+            _ => Ok(None),
+        }
+    }
+}
+
+// Range la réponse d'un endpoint : `Some` met fin à la recherche, `None` passe au suivant
+fn settle(
+    result: TransactionResult,
+    last_error: &mut Option<ClientError>,
+    seen_null: &mut bool,
+) -> Option<TransactionResult> {
+    match result {
+        Ok(Some(tx)) => Some(Ok(Some(tx))),
+        Ok(None) => {
+            *seen_null = true;
+            None
+        }
+        Err(e) if is_retryable(classify_error(&e)) => {
+            *last_error = Some(e);
+            None
+        }
+        Err(e) => Some(Err(e)),
+    }
+}
+
+fn no_endpoint() -> ClientError {
+    ClientError::from(std::io::Error::other("no RPC endpoint available"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::mock_rpc::{MockRpcServer, NODE_BEHIND, Reply};
+    use crate::infra::rate_limit::{ProviderProfile, RateLimiter};
+    use serde_json::{Value, json};
+    use solana_client::nonblocking::rpc_client::RpcClient;
+
+    fn endpoint(server: &MockRpcServer) -> ThrottledRpc {
+        let limiter = RateLimiter::new(ProviderProfile::new("test", 1_000.0, 8));
+        ThrottledRpc::new(RpcClient::new(server.url()), Arc::new(limiter))
+    }
+
+    // getSlot lent : l'endpoint passe derrière les autres au classement par latence
+    fn node(
+        slot: u64,
+        slot_delay: Duration,
+        reply: impl Fn(&str) -> Reply + Send + Sync + 'static,
+    ) -> impl Fn(&str, &Value) -> Reply + Send + Sync + 'static {
+        move |method, _| match method {
+            "getSlot" => Reply::Slow(slot_delay, Box::new(Reply::Result(json!(slot)))),
+            other => reply(other),
+        }
+    }
+
+    fn accounts() -> Reply {
+        Reply::Result(json!({ "context": { "slot": 100 }, "value": [null] }))
+    }
+
+    fn transaction() -> Reply {
+        Reply::Result(json!({
+            "slot": 100,
+            "blockTime": null,
+            "transaction": ["", "base64"],
+            "meta": null,
+        }))
+    }
+
+    fn options(hedge_after: Option<Duration>) -> PoolOptions {
+        PoolOptions {
+            max_slot_lag: 50,
+            hedge_after,
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_the_next_endpoint_on_transient_errors() {
+        let failing = MockRpcServer::start(node(100, Duration::ZERO, |_| Reply::Error(NODE_BEHIND))).await;
+        let healthy = MockRpcServer::start(node(100, Duration::from_millis(50), |_| accounts())).await;
+        let pool = RpcPool::new(vec![endpoint(&failing), endpoint(&healthy)], options(None));
+
+        let accounts = pool.get_multiple_accounts(&[Pubkey::new_unique()]).await.unwrap();
+
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(failing.calls("getMultipleAccounts"), 1);
+        assert_eq!(healthy.calls("getMultipleAccounts"), 1);
+    }
+
+    #[tokio::test]
+    async fn reports_the_last_error_when_every_endpoint_fails() {
+        let first = MockRpcServer::start(node(100, Duration::ZERO, |_| Reply::Error(NODE_BEHIND))).await;
+        let second = MockRpcServer::start(node(100, Duration::ZERO, |_| Reply::Error(NODE_BEHIND))).await;
+        let pool = RpcPool::new(vec![endpoint(&first), endpoint(&second)], options(None));
+
+        assert!(pool.get_multiple_accounts(&[Pubkey::new_unique()]).await.is_err());
+        assert!(pool.get_transaction(&Signature::default(), RpcTransactionConfig::default()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn lagging_endpoints_are_tried_last() {
+        // Le nœud en retard répond le plus vite, mais 1 000 slots derrière
+        let lagging = MockRpcServer::start(node(1_000, Duration::ZERO, |_| accounts())).await;
+        let synced = MockRpcServer::start(node(2_000, Duration::from_millis(50), |_| accounts())).await;
+        let pool = RpcPool::new(vec![endpoint(&lagging), endpoint(&synced)], options(None));
+
+        for _ in 0..3 {
+            pool.get_multiple_accounts(&[Pubkey::new_unique()]).await.unwrap();
+        }
+
+        assert_eq!(synced.calls("getMultipleAccounts"), 3);
+        assert_eq!(lagging.calls("getMultipleAccounts"), 0);
+    }
+
+    #[tokio::test]
+    async fn hedges_a_slow_transaction_on_the_second_endpoint() {
+        let slow = MockRpcServer::start(node(100, Duration::ZERO, |_| {
+            Reply::Slow(Duration::from_secs(2), Box::new(transaction()))
+        }))
+        .await;
+        let fast = MockRpcServer::start(node(100, Duration::from_millis(50), |_| transaction())).await;
+        let pool = RpcPool::new(vec![endpoint(&slow), endpoint(&fast)], options(Some(Duration::from_millis(50))));
+
+        let started = Instant::now();
+        let tx = pool.get_transaction(&Signature::default(), RpcTransactionConfig::default()).await.unwrap();
+
+        assert_eq!(tx.map(|tx| tx.slot), Some(100));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(slow.calls("getTransaction"), 1);
+        assert_eq!(fast.calls("getTransaction"), 1);
+    }
+
+    #[tokio::test]
+    async fn hedged_pair_failing_is_an_error_not_a_missing_transaction() {
+        let slow = MockRpcServer::start(node(100, Duration::ZERO, |_| {
+            Reply::Slow(Duration::from_millis(200), Box::new(Reply::Error(NODE_BEHIND)))
+        }))
+        .await;
+        let failing = MockRpcServer::start(node(100, Duration::from_millis(50), |_| Reply::Error(NODE_BEHIND))).await;
+        let pool = RpcPool::new(vec![endpoint(&slow), endpoint(&failing)], options(Some(Duration::from_millis(50))));
+
+        let result = pool.get_transaction(&Signature::default(), RpcTransactionConfig::default()).await;
+
+        assert!(result.is_err());
+        assert_eq!(failing.calls("getTransaction"), 1);
+    }
+
+    #[tokio::test]
+    async fn null_from_one_endpoint_wins_over_errors_from_others() {
+        let unknown = MockRpcServer::start(node(100, Duration::ZERO, |_| Reply::Result(Value::Null))).await;
+        let failing = MockRpcServer::start(node(100, Duration::from_millis(50), |_| Reply::Error(NODE_BEHIND))).await;
+        let pool = RpcPool::new(vec![endpoint(&unknown), endpoint(&failing)], options(None));
+
+        let result = pool.get_transaction(&Signature::default(), RpcTransactionConfig::default()).await;
+
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[tokio::test]
+    async fn empty_pool_has_no_endpoint() {
+        let pool = RpcPool::new(Vec::new(), PoolOptions::default());
+
+        assert!(pool.get_transaction(&Signature::default(), RpcTransactionConfig::default()).await.is_err());
+        assert!(pool.get_slot().await.is_err());
+    }
+}
@@ -1,22 +1,25 @@
+use crate::domain::history::{HistoryLimit, SignatureHistory};
 use crate::error::{self, Result};
-use solana_client::nonblocking::rpc_client::RpcClient;
+use crate::infra::rpc_backend::RpcBackend;
+use crate::infra::tx_cache::TxCache;
+use solana_sdk::commitment_config::{CommitmentConfig, CommitmentLevel};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
 use solana_sdk::{account::Account, pubkey::Pubkey};
 use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
 use solana_transaction_status_client_types::EncodedTransaction;
 use solana_transaction_status_client_types::UiMessage;
-use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_config::{RpcBlockConfig, RpcTransactionConfig};
+use solana_transaction_status_client_types::TransactionDetails;
+use std::collections::{HashMap, HashSet};
+use crate::domain::fetch::{FailureKind, FetchFailure, FetchedTransactions};
+use crate::infra::retry::{RetryPolicy, classify_error, is_retryable};
 use solana_client::client_error::ClientError;
-use tokio::time::interval;
-use tokio::time::Duration;
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use tokio::time::Interval;
 use solana_sdk::signature::Signature;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::str::FromStr;
 use futures::{stream, StreamExt};
 
-pub async fn fetch_account(rpc: &RpcClient, address: Pubkey) -> Result<Account> {
+pub async fn fetch_account(rpc: &dyn RpcBackend, address: Pubkey) -> Result<Account> {
     let mut accounts = rpc.get_multiple_accounts(&[address]).await?;
     accounts
         .pop()
@@ -24,6 +27,78 @@ pub async fn fetch_account(rpc: &RpcClient, address: Pubkey) -> Result<Account>
         .ok_or(error::Error::TokenNotFound(address))
 }
 
+pub async fn fetch_signature_history(
+    rpc: &dyn RpcBackend,
+    address: &Pubkey,
+    limit: &HistoryLimit,
+) -> Result<SignatureHistory> {
+    const PAGE_SIZE: usize = 1000;
+
+    let mut history = SignatureHistory::default();
+    let mut before: Option<Signature> = None;
+
+    loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until: limit.until_signature,
+            limit: Some(PAGE_SIZE),
+            commitment: Some(rpc.commitment()),
+        };
+        let page = rpc
+            .get_signatures_for_address_with_config(address, config)
+            .await?;
+        history.pages += 1;
+
+        let page_len = page.len();
+        before = match page.last() {
+            Some(last) => Some(Signature::from_str(&last.signature).map_err(|e| ClientError::from(std::io::Error::other(e)))?),
+            None => None,
+        };
+
+        let mut stop = false;
+        for status in page {
+            if limit.is_before_range(&status) {
+                stop = true;
+                break;
+            }
+            if limit.is_after_range(&status) {
+                continue;
+            }
+            history.signatures.push(status);
+
+            if let Some(hard_cap) = limit.hard_cap
+                && history.signatures.len() > hard_cap
+            {
+                return Err(error::Error::TooManyTransactions {
+                    limit: hard_cap,
+                    transactions_fetched: history.signatures.len(),
+                });
+            }
+            if limit.is_full(history.signatures.len()) {
+                stop = true;
+                break;
+            }
+        }
+
+        if stop {
+            break;
+        }
+        if page_len < PAGE_SIZE {
+            // `until` borne le parcours : on n'a pas forcément atteint la création
+            history.complete = limit.until_signature.is_none();
+            break;
+        }
+    }
+
+    tracing::info!(
+        pages = history.pages,
+        signatures = history.signatures.len(),
+        complete = history.complete,
+        "📜 historique de signatures récupéré"
+    );
+    Ok(history)
+}
+
 pub fn extract_account_keys(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Option<Vec<String>> {
     let enc = &tx.transaction.transaction; // Option<EncodedTransaction>
 
@@ -42,20 +117,58 @@ pub fn extract_account_keys(tx: &EncodedConfirmedTransactionWithStatusMeta) -> O
     }
 }
 
+// Position dans le bloc des signatures qui nous intéressent ; vide si le bloc est indisponible
+pub async fn fetch_block_positions(rpc: &dyn RpcBackend, slot: u64, wanted: &HashSet<&str>) -> HashMap<String, u32> {
+    // getBlock refuse le niveau `processed`
+    let commitment = match rpc.commitment().commitment {
+        CommitmentLevel::Processed => CommitmentConfig::confirmed(),
+        _ => rpc.commitment(),
+    };
+    let config = RpcBlockConfig {
+        transaction_details: Some(TransactionDetails::Signatures),
+        rewards: Some(false),
+        commitment: Some(commitment),
+        max_supported_transaction_version: Some(0),
+        ..Default::default()
+    };
+    match rpc.get_block(slot, config).await {
+        Ok(block) => block
+            .signatures
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, s)| wanted.contains(s.as_str()))
+            .map(|(i, s)| (s, i as u32))
+            .collect(),
+        Err(e) => {
+            tracing::warn!(slot, "bloc non récupéré, ordre de récupération conservé: {e}");
+            HashMap::new()
+        }
+    }
+}
+
 pub async fn retrieve_transactions(
-    rpc: &RpcClient,
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
     signatures: Vec<String>,
     config: RpcTransactionConfig,
-) -> std::result::Result<Vec<EncodedConfirmedTransactionWithStatusMeta>, ClientError> {
-    const MAX_CONCURRENT: usize = 10;
-    const TARGET_RPS: u32 = 13; // plafond global de ton RPC
-    let tick_every = Duration::from_millis(1_000 / TARGET_RPS as u64);
+    retry: &RetryPolicy,
+) -> FetchedTransactions {
+    // Les transactions déjà vues viennent du cache, seules les autres partent au RPC
+    let mut cached = cache.map(|c| c.get_many(&signatures)).unwrap_or_default();
+    let (known, missing): (Vec<_>, Vec<_>) = signatures
+        .into_iter()
+        .enumerate()
+        .partition(|(_, s)| cached.contains_key(s));
+    if cache.is_some() {
+        tracing::info!(cached = known.len(), to_fetch = missing.len(), "💾 cache de transactions consulté");
+    }
 
-    // Crée un intervalle global et partage-le entre futures
-    let ticker = Arc::new(Mutex::new(interval(tick_every)));
+    // La cadence est imposée par le limiteur partagé, on ne fait que borner le nombre de futures
+    let max_concurrent = rpc.max_concurrent();
 
     // Barre de progression
-    let pb = ProgressBar::new(signatures.len() as u64);
+    let pb = ProgressBar::new(missing.len() as u64);
     pb.set_style(
         ProgressStyle::with_template("[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} ({percent}%)")
             .unwrap()
@@ -63,46 +176,221 @@ pub async fn retrieve_transactions(
     );
 
     // Création des futures
-    let futs = signatures.into_iter().map(|s| {
+    let futs = missing.into_iter().map(|(index, s)| {
         let cfg = config.clone();
         let pb = pb.clone();
-        let ticker = ticker.clone();
 
         async move {
-            // Attendre un tick global → cadence max = TARGET_RPS
-            {
-                let mut t: tokio::sync::MutexGuard<'_, Interval> = ticker.lock().await;
-                t.tick().await;
-            }
-
-            // Parse de la signature
-            let sig = Signature::from_str(&s)
-                .map_err(|e| ClientError::from(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
-
-            // Appel RPC
-            let result = rpc.get_transaction_with_config(&sig, cfg).await;
-
+            let result = fetch_transaction_with_retry(rpc, &s, cfg, retry).await;
             pb.inc(1);
-            result
+            (index, s, result)
         }
     });
 
     // Exécuter avec parallélisme borné
-    let results = stream::iter(futs)
-        .buffer_unordered(MAX_CONCURRENT)
+    let mut results = stream::iter(futs)
+        .buffer_unordered(max_concurrent)
         .collect::<Vec<_>>()
         .await;
 
     pb.finish_with_message("✅ Analyse terminée");
 
-    // Collecter les OK
-    let mut out = Vec::new();
-    for r in results {
-        match r {
-            Ok(tx) => out.push(tx),
-            Err(e) => tracing::warn!("RPC error: {e}"),
+    if let Some(cache) = cache.filter(|_| is_cacheable(&config)) {
+        let fresh: Vec<(&str, &EncodedConfirmedTransactionWithStatusMeta)> = results
+            .iter()
+            .filter_map(|(_, s, r)| r.as_ref().ok().map(|tx| (s.as_str(), tx)))
+            .collect();
+        cache.put_many(&fresh);
+    }
+
+    // Remettre dans l'ordre des signatures demandées
+    results.extend(known.into_iter().filter_map(|(index, s)| {
+        let tx = cached.remove(&s)?;
+        Some((index, s, Ok(tx)))
+    }));
+    results.sort_by_key(|(index, _, _)| *index);
+
+    let mut out = FetchedTransactions::default();
+    for (_, signature, result) in results {
+        match result {
+            Ok(tx) => out.transactions.push(tx),
+            Err((kind, attempts, message)) => {
+                tracing::warn!(%signature, %kind, attempts, "transaction non récupérée: {message}");
+                out.failures.push(FetchFailure { signature, kind, attempts, message });
+            }
+        }
+    }
+
+    if !out.is_complete() {
+        tracing::warn!("⚠️ {} transaction(s) manquante(s), analyse incomplète", out.failures.len());
+    }
+    out
+}
+
+pub async fn fetch_transaction(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    signature: &Signature,
+    config: RpcTransactionConfig,
+) -> Result<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+    let key = signature.to_string();
+    if let Some(tx) = cache.and_then(|c| c.get(&key)) {
+        return Ok(Some(tx));
+    }
+    let tx = rpc.get_transaction(signature, config).await?;
+    if let (Some(cache), Some(tx)) = (cache.filter(|_| is_cacheable(&config)), tx.as_ref()) {
+        cache.put_many(&[(key.as_str(), tx)]);
+    }
+    Ok(tx)
+}
+
+// Seules les transactions confirmées sont immuables
+fn is_cacheable(config: &RpcTransactionConfig) -> bool {
+    !config.commitment.is_some_and(|c| c.commitment == CommitmentLevel::Processed)
+}
+
+async fn fetch_transaction_with_retry(
+    rpc: &dyn RpcBackend,
+    signature: &str,
+    config: RpcTransactionConfig,
+    retry: &RetryPolicy,
+) -> std::result::Result<EncodedConfirmedTransactionWithStatusMeta, (FailureKind, u32, String)> {
+    let sig = Signature::from_str(signature)
+        .map_err(|e| (FailureKind::InvalidSignature, 0, e.to_string()))?;
+
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+        let result = rpc.get_transaction(&sig, config).await;
+
+        let (kind, message) = match result {
+            Ok(Some(tx)) => return Ok(tx),
+            Ok(None) => (FailureKind::NotAvailable, "transaction not available yet".to_string()),
+            Err(e) => (classify_error(&e), e.to_string()),
+        };
+
+        // Une transaction confirmée peut manquer quelques instants sur un nœud en retard
+        let retryable = is_retryable(kind) || kind == FailureKind::NotAvailable;
+        if !retryable || attempt > retry.max_retries {
+            return Err((kind, attempt, message));
         }
+        tokio::time::sleep(retry.backoff(attempt - 1)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::memory_rpc::MemoryRpc;
+    use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+
+    const FIRST_SLOT: u64 = 1_000;
+
+    fn signature(index: u32) -> Signature {
+        let mut bytes = [1u8; 64];
+        bytes[..4].copy_from_slice(&index.to_le_bytes());
+        Signature::from(bytes)
+    }
+
+    // `count` signatures, une par slot, la plus ancienne au slot FIRST_SLOT
+    fn rpc_with_history(address: Pubkey, count: u32) -> MemoryRpc {
+        let statuses = (0..count).map(|i| RpcConfirmedTransactionStatusWithSignature {
+            signature: signature(i).to_string(),
+            slot: FIRST_SLOT + i as u64,
+            err: None,
+            memo: None,
+            block_time: Some(1_750_000_000 + i as i64),
+            confirmation_status: None,
+        });
+        MemoryRpc::new().with_signatures(address, statuses)
+    }
+
+    #[test]
+    fn only_confirmed_transactions_are_cacheable() {
+        let config = |commitment: Option<CommitmentConfig>| RpcTransactionConfig {
+            commitment,
+            ..RpcTransactionConfig::default()
+        };
+
+        assert!(!is_cacheable(&config(Some(CommitmentConfig::processed()))));
+        assert!(is_cacheable(&config(Some(CommitmentConfig::confirmed()))));
+        assert!(is_cacheable(&config(Some(CommitmentConfig::finalized()))));
+        // Sans niveau explicite, c'est celui du client qui s'applique
+        assert!(is_cacheable(&config(None)));
     }
 
-    Ok(out)
-}
\ No newline at end of file
+    #[tokio::test]
+    async fn walks_every_page_down_to_the_first_signature() {
+        let address = Pubkey::new_unique();
+        let rpc = rpc_with_history(address, 2_500);
+
+        let history = fetch_signature_history(&rpc, &address, &HistoryLimit::default()).await.unwrap();
+
+        assert_eq!(history.signatures.len(), 2_500);
+        assert_eq!(history.pages, 3);
+        assert!(history.complete);
+        assert_eq!(history.signatures.last().unwrap().slot, FIRST_SLOT);
+    }
+
+    #[tokio::test]
+    async fn stops_at_max_signatures_without_claiming_completeness() {
+        let address = Pubkey::new_unique();
+        let rpc = rpc_with_history(address, 2_500);
+        let limit = HistoryLimit {
+            max_signatures: Some(1_200),
+            ..Default::default()
+        };
+
+        let history = fetch_signature_history(&rpc, &address, &limit).await.unwrap();
+
+        assert_eq!(history.signatures.len(), 1_200);
+        assert_eq!(history.pages, 2);
+        assert!(!history.complete);
+    }
+
+    #[tokio::test]
+    async fn keeps_only_the_slot_range() {
+        let address = Pubkey::new_unique();
+        let rpc = rpc_with_history(address, 2_500);
+        let limit = HistoryLimit {
+            min_slot: Some(FIRST_SLOT + 100),
+            max_slot: Some(FIRST_SLOT + 199),
+            ..Default::default()
+        };
+
+        let history = fetch_signature_history(&rpc, &address, &limit).await.unwrap();
+
+        assert_eq!(history.signatures.len(), 100);
+        assert!(history.signatures.iter().all(|s| (FIRST_SLOT + 100..FIRST_SLOT + 200).contains(&s.slot)));
+        assert!(!history.complete);
+    }
+
+    #[tokio::test]
+    async fn hard_cap_rejects_oversized_histories() {
+        let address = Pubkey::new_unique();
+        let rpc = rpc_with_history(address, 1_500);
+        let limit = HistoryLimit {
+            hard_cap: Some(1_000),
+            ..Default::default()
+        };
+
+        let result = fetch_signature_history(&rpc, &address, &limit).await;
+
+        assert!(matches!(result, Err(error::Error::TooManyTransactions { limit: 1_000, .. })));
+    }
+
+    #[tokio::test]
+    async fn until_signature_bounds_the_walk() {
+        let address = Pubkey::new_unique();
+        let rpc = rpc_with_history(address, 50);
+        let limit = HistoryLimit {
+            until_signature: Some(signature(39)),
+            ..Default::default()
+        };
+
+        let history = fetch_signature_history(&rpc, &address, &limit).await.unwrap();
+
+        assert_eq!(history.signatures.len(), 10);
+        assert!(!history.complete);
+    }
+}
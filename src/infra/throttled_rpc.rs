@@ -0,0 +1,104 @@
+use crate::domain::fetch::FailureKind;
+use crate::infra::rate_limit::RateLimiter;
+use crate::infra::retry::classify_error;
+use crate::infra::rpc_backend::RpcBackend;
+use solana_client::client_error::Result as ClientResult;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::{RpcBlockConfig, RpcProgramAccountsConfig, RpcTransactionConfig};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_transaction_status_client_types::{EncodedConfirmedTransactionWithStatusMeta, UiConfirmedBlock};
+use std::sync::Arc;
+
+// Point de passage unique vers le RPC : chaque appel consomme des crédits du limiteur
+pub struct ThrottledRpc {
+    client: RpcClient,
+    limiter: Arc<RateLimiter>,
+}
+
+impl ThrottledRpc {
+    pub fn new(client: RpcClient, limiter: Arc<RateLimiter>) -> Self {
+        Self { client, limiter }
+    }
+
+    pub fn limiter(&self) -> &RateLimiter {
+        &self.limiter
+    }
+
+    pub fn url(&self) -> String {
+        self.client.url()
+    }
+
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.client.commitment()
+    }
+
+    pub async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> ClientResult<Vec<Option<Account>>> {
+        let _permit = self.limiter.acquire(RpcRequest::GetMultipleAccounts).await;
+        let result = self.client.get_multiple_accounts(addresses).await;
+        self.observe(&result);
+        result
+    }
+
+    pub async fn get_signatures_for_address_with_config(
+        &self,
+        address: &Pubkey,
+        config: GetConfirmedSignaturesForAddress2Config,
+    ) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        let _permit = self.limiter.acquire(RpcRequest::GetSignaturesForAddress).await;
+        let result = self
+            .client
+            .get_signatures_for_address_with_config(address, config)
+            .await;
+        self.observe(&result);
+        result
+    }
+
+    pub async fn get_transaction(
+        &self,
+        signature: &Signature,
+        config: RpcTransactionConfig,
+    ) -> ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+        let _permit = self.limiter.acquire(RpcRequest::GetTransaction).await;
+        let result = RpcBackend::get_transaction(&self.client, signature, config).await;
+        self.observe(&result);
+        result
+    }
+
+    pub async fn get_slot(&self) -> ClientResult<u64> {
+        let _permit = self.limiter.acquire(RpcRequest::GetSlot).await;
+        let result = self.client.get_slot().await;
+        self.observe(&result);
+        result
+    }
+
+    pub async fn get_block(&self, slot: u64, config: RpcBlockConfig) -> ClientResult<UiConfirmedBlock> {
+        let _permit = self.limiter.acquire(RpcRequest::GetBlock).await;
+        let result = self.client.get_block_with_config(slot, config).await;
+        self.observe(&result);
+        result
+    }
+
+    pub async fn get_program_accounts(
+        &self,
+        program: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> ClientResult<Vec<(Pubkey, Account)>> {
+        let _permit = self.limiter.acquire(RpcRequest::GetProgramAccounts).await;
+        let result = self.client.get_program_accounts_with_config(program, config).await;
+        self.observe(&result);
+        result
+    }
+
+    fn observe<T>(&self, result: &ClientResult<T>) {
+        match result {
+            Ok(_) => self.limiter.on_success(),
+            Err(e) if classify_error(e) == FailureKind::RateLimited => self.limiter.on_rate_limited(),
+            Err(_) => {}
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use crate::error::Result;
+use rusqlite::{Connection, OptionalExtension, params};
+use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ZSTD_LEVEL: i32 = 3;
+// Après éviction on redescend sous ce ratio de la taille max pour ne pas évincer à chaque lot
+const EVICTION_TARGET: f64 = 0.9;
+
+#[derive(Debug, Clone)]
+pub struct CacheOptions {
+    pub dir: PathBuf,
+    pub max_bytes: u64,
+    // Ignore les entrées existantes mais continue d'écrire les transactions téléchargées
+    pub refresh: bool,
+}
+
+// Cache local des transactions confirmées, indexé par signature.
+// Les valeurs sont le JSON de la transaction compressé en zstd.
+pub struct TxCache {
+    conn: Mutex<Connection>,
+    max_bytes: u64,
+    refresh: bool,
+}
+
+impl TxCache {
+    pub fn open(options: &CacheOptions) -> Result<Self> {
+        std::fs::create_dir_all(&options.dir)?;
+        let conn = Connection::open(options.dir.join("transactions.sqlite"))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS transactions (
+                 signature   TEXT PRIMARY KEY,
+                 data        BLOB NOT NULL,
+                 size        INTEGER NOT NULL,
+                 last_access INTEGER NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS transactions_last_access ON transactions (last_access);",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            max_bytes: options.max_bytes,
+            refresh: options.refresh,
+        })
+    }
+
+    pub fn get(&self, signature: &str) -> Option<EncodedConfirmedTransactionWithStatusMeta> {
+        if self.refresh {
+            return None;
+        }
+        let conn = self.conn.lock().unwrap();
+        let data: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT data FROM transactions WHERE signature = ?1",
+                params![signature],
+                |row| row.get(0),
+            )
+            .optional()
+            .unwrap_or_else(|e| {
+                tracing::warn!(%signature, "lecture du cache impossible: {e}");
+                None
+            });
+        let tx = data.and_then(|data| match decode(&data) {
+            Ok(tx) => Some(tx),
+            Err(e) => {
+                tracing::warn!(%signature, "entrée de cache illisible: {e}");
+                None
+            }
+        })?;
+        let _ = conn.execute(
+            "UPDATE transactions SET last_access = ?2 WHERE signature = ?1",
+            params![signature, now()],
+        );
+        Some(tx)
+    }
+
+    pub fn get_many(&self, signatures: &[String]) -> HashMap<String, EncodedConfirmedTransactionWithStatusMeta> {
+        signatures
+            .iter()
+            .filter_map(|signature| self.get(signature).map(|tx| (signature.clone(), tx)))
+            .collect()
+    }
+
+    pub fn put_many(&self, entries: &[(&str, &EncodedConfirmedTransactionWithStatusMeta)]) {
+        if entries.is_empty() {
+            return;
+        }
+        if let Err(e) = self.try_put_many(entries) {
+            tracing::warn!("écriture du cache impossible: {e}");
+        }
+    }
+
+    fn try_put_many(&self, entries: &[(&str, &EncodedConfirmedTransactionWithStatusMeta)]) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let db_tx = conn.transaction()?;
+        {
+            let mut stmt = db_tx.prepare(
+                "INSERT OR REPLACE INTO transactions (signature, data, size, last_access) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            let timestamp = now();
+            for (signature, tx) in entries {
+                let data = encode(tx)?;
+                stmt.execute(params![signature, data, data.len() as i64, timestamp])?;
+            }
+        }
+        db_tx.commit()?;
+        evict(&mut conn, self.max_bytes)
+    }
+}
+
+// Supprime les entrées les moins récemment lues jusqu'à repasser sous la limite
+fn evict(conn: &mut Connection, max_bytes: u64) -> Result<()> {
+    let total: i64 = conn.query_row("SELECT COALESCE(SUM(size), 0) FROM transactions", [], |row| row.get(0))?;
+    if (total as u64) <= max_bytes {
+        return Ok(());
+    }
+    let to_free = total as u64 - (max_bytes as f64 * EVICTION_TARGET) as u64;
+
+    let db_tx = conn.transaction()?;
+    let victims: Vec<String> = {
+        let mut stmt = db_tx.prepare("SELECT signature, size FROM transactions ORDER BY last_access ASC")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+        let mut freed = 0u64;
+        let mut victims = Vec::new();
+        for row in rows {
+            let (signature, size) = row?;
+            if freed >= to_free {
+                break;
+            }
+            freed += size as u64;
+            victims.push(signature);
+        }
+        victims
+    };
+    for signature in &victims {
+        db_tx.execute("DELETE FROM transactions WHERE signature = ?1", params![signature])?;
+    }
+    db_tx.commit()?;
+    tracing::info!(evicted = victims.len(), "🧹 cache de transactions réduit");
+    Ok(())
+}
+
+fn encode(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Result<Vec<u8>> {
+    let json = serde_json::to_vec(tx).map_err(std::io::Error::other)?;
+    Ok(zstd::encode_all(json.as_slice(), ZSTD_LEVEL)?)
+}
+
+fn decode(data: &[u8]) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let json = zstd::decode_all(data)?;
+    Ok(serde_json::from_slice(&json).map_err(std::io::Error::other)?)
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fetch::FetchedTransactions;
+    use crate::infra::retry::RetryPolicy;
+    use crate::infra::solana_rpc::{fetch_transaction, retrieve_transactions};
+    use crate::platforms::pumpfun::sample::SampleLaunch;
+    use solana_client::rpc_config::RpcTransactionConfig;
+    use solana_sdk::commitment_config::CommitmentConfig;
+
+    // Répertoire de cache propre au test, supprimé à la fin
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("tx_cache_{name}_{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            Self(dir)
+        }
+
+        fn options(&self, max_bytes: u64, refresh: bool) -> CacheOptions {
+            CacheOptions { dir: self.0.clone(), max_bytes, refresh }
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn sample_tx() -> EncodedConfirmedTransactionWithStatusMeta {
+        SampleLaunch::new().transactions[0].encoded()
+    }
+
+    fn stored(cache: &TxCache) -> Vec<(String, i64)> {
+        let conn = cache.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT signature, size FROM transactions ORDER BY signature").unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn transactions_round_trip_through_zstd() {
+        let dir = TempDir::new("round_trip");
+        let cache = TxCache::open(&dir.options(u64::MAX, false)).unwrap();
+        let tx = sample_tx();
+
+        cache.put_many(&[("sig", &tx)]);
+
+        let data: Vec<u8> = cache
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT data FROM transactions WHERE signature = 'sig'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(data[..4], [0x28, 0xb5, 0x2f, 0xfd]);
+        assert!(data.len() < serde_json::to_vec(&tx).unwrap().len());
+        let cached = cache.get("sig").unwrap();
+        assert_eq!(serde_json::to_value(cached).unwrap(), serde_json::to_value(&tx).unwrap());
+        assert!(cache.get("unknown").is_none());
+    }
+
+    #[test]
+    fn refresh_ignores_entries_but_still_writes() {
+        let dir = TempDir::new("refresh");
+        let tx = sample_tx();
+        TxCache::open(&dir.options(u64::MAX, false)).unwrap().put_many(&[("old", &tx)]);
+
+        let refreshing = TxCache::open(&dir.options(u64::MAX, true)).unwrap();
+        assert!(refreshing.get("old").is_none());
+        assert!(refreshing.get_many(&["old".to_string()]).is_empty());
+        refreshing.put_many(&[("new", &tx)]);
+        drop(refreshing);
+
+        let cache = TxCache::open(&dir.options(u64::MAX, false)).unwrap();
+        assert!(cache.get("old").is_some());
+        assert!(cache.get("new").is_some());
+    }
+
+    #[test]
+    fn exceeding_the_size_limit_evicts_the_least_recently_read_down_to_90_percent() {
+        let dir = TempDir::new("eviction");
+        let tx = sample_tx();
+        let signatures: Vec<String> = (0..10).map(|i| format!("sig{i}")).collect();
+        {
+            let cache = TxCache::open(&dir.options(u64::MAX, false)).unwrap();
+            let entries: Vec<(&str, &EncodedConfirmedTransactionWithStatusMeta)> =
+                signatures.iter().map(|s| (s.as_str(), &tx)).collect();
+            cache.put_many(&entries);
+            // Dates d'accès croissantes : sig0 est la plus ancienne
+            let conn = cache.conn.lock().unwrap();
+            for (i, signature) in signatures.iter().enumerate() {
+                conn.execute(
+                    "UPDATE transactions SET last_access = ?2 WHERE signature = ?1",
+                    params![signature, i as i64],
+                )
+                .unwrap();
+            }
+        }
+        let size = stored(&TxCache::open(&dir.options(u64::MAX, false)).unwrap())[0].1 as u64;
+
+        // Dix entrées tiennent tout juste ; la onzième déclenche l'éviction
+        let max_bytes = 10 * size;
+        let cache = TxCache::open(&dir.options(max_bytes, false)).unwrap();
+        assert!(cache.get("sig0").is_some());
+        cache.put_many(&[("sig10", &tx)]);
+
+        let remaining = stored(&cache);
+        let total: i64 = remaining.iter().map(|(_, size)| size).sum();
+        assert!(total as f64 <= max_bytes as f64 * EVICTION_TARGET);
+        let kept: Vec<&str> = remaining.iter().map(|(s, _)| s.as_str()).collect();
+        // 11 entrées pour 9 places sous la cible : sig1 et sig2 partent, sig0 vient d'être lue
+        assert_eq!(kept, vec!["sig0", "sig10", "sig3", "sig4", "sig5", "sig6", "sig7", "sig8", "sig9"]);
+    }
+
+    #[test]
+    fn staying_under_the_limit_evicts_nothing() {
+        let dir = TempDir::new("under_limit");
+        let tx = sample_tx();
+        let cache = TxCache::open(&dir.options(u64::MAX, false)).unwrap();
+
+        cache.put_many(&[("a", &tx), ("b", &tx)]);
+
+        assert_eq!(stored(&cache).len(), 2);
+    }
+
+    fn tx_config(commitment: CommitmentConfig) -> RpcTransactionConfig {
+        RpcTransactionConfig {
+            commitment: Some(commitment),
+            ..RpcTransactionConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn processed_transactions_are_never_cached() {
+        let dir = TempDir::new("processed");
+        let cache = TxCache::open(&dir.options(u64::MAX, false)).unwrap();
+        let launch = SampleLaunch::new();
+        let rpc = launch.rpc();
+        let [first, second] = [&launch.transactions[0].signature, &launch.transactions[1].signature];
+
+        fetch_transaction(&rpc, Some(&cache), first, tx_config(CommitmentConfig::processed())).await.unwrap();
+        fetch_transaction(&rpc, Some(&cache), second, tx_config(CommitmentConfig::confirmed())).await.unwrap();
+
+        assert!(cache.get(&first.to_string()).is_none());
+        assert!(cache.get(&second.to_string()).is_some());
+    }
+
+    #[tokio::test]
+    async fn cached_transactions_are_not_fetched_again() {
+        let dir = TempDir::new("retrieve");
+        let cache = TxCache::open(&dir.options(u64::MAX, false)).unwrap();
+        let launch = SampleLaunch::new();
+        let signatures: Vec<String> = launch.transactions.iter().map(|tx| tx.signature.to_string()).collect();
+        let config = tx_config(CommitmentConfig::confirmed());
+        let policy = RetryPolicy { max_retries: 0, ..RetryPolicy::default() };
+
+        let first = retrieve_transactions(&launch.rpc(), Some(&cache), signatures.clone(), config, &policy).await;
+        // Le second nœud n'a aucune transaction : tout doit venir du cache, dans l'ordre demandé
+        let second =
+            retrieve_transactions(&launch.rpc_without_transactions(), Some(&cache), signatures, config, &policy).await;
+
+        assert!(first.is_complete() && second.is_complete());
+        let slots = |fetched: &FetchedTransactions| fetched.transactions.iter().map(|tx| tx.slot).collect::<Vec<_>>();
+        assert_eq!(slots(&second), slots(&first));
+        assert_eq!(second.transactions.len(), launch.transactions.len());
+    }
+}
@@ -0,0 +1,10 @@
+pub mod cli;
+pub mod domain;
+pub mod error;
+pub mod infra;
+pub mod platforms;
+pub mod report;
+pub mod services;
+
+// Moteur de courbe pump.fun (cotations, frais, impact de prix, graduation) utilisable hors du binaire
+pub use platforms::pumpfun::curve;
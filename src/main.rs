@@ -1,51 +1,229 @@
+use clap::Parser;
 use dotenv::dotenv;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{lamports, pubkey::Pubkey};
-use std::{collections::HashMap, str::FromStr};
-mod domain;
-mod error;
-mod infra;
+use solana_client::rpc_client::RpcClientConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::sync::Arc;
+use std::time::Duration;
+use solana_token_scanner::{cli, domain, error, infra, report, services};
 use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
-mod services;
-use services::preflight::run_analysis;
-mod platforms;
+use services::preflight::{fetch_bonding_curve, run_analysis};
+use services::creator_history::{CreatorHistoryOptions, creator_history};
+use services::batch::scan_batch;
+use services::holders::holder_snapshot;
+use services::watch::{WatchOptions, watch_launches};
+use cli::{Cli, Command, OutputFormat, quote_amount, read_mint_list};
+use domain::base58::parse_pubkey;
+use infra::fixtures::{FixtureStore, RecordingSender, ReplayRpc};
+use infra::rate_limit::RateLimiter;
+use infra::retry::RetryPolicy;
+use infra::rpc_backend::RpcBackend;
+use infra::rpc_pool::RpcPool;
+use infra::throttled_rpc::ThrottledRpc;
+use infra::tx_cache::TxCache;
+use infra::frame_writer::write_frame;
+use domain::candles::{Resolution, build_candles, candles_frame};
+use domain::ledger::{CostMethod, WalletLedger};
+use domain::trades::trades_frame;
+use solana_token_scanner::curve::{CurveState, Fees};
 use solana_client::rpc_config::RpcTransactionConfig;
-use solana_sdk::commitment_config::CommitmentConfig;
 use solana_transaction_status_client_types::UiTransactionEncoding;
-use domain::token_stats::TokenStats;
 
 #[tokio::main]
-async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+async fn main() {
     dotenv().ok();
     tracing_subscriber::registry()
         .with(EnvFilter::new("info"))
         .with(fmt::layer().compact())
         .init();
 
-    let rpc_url = std::env::var("RPC_URL").unwrap();
-    let rpc_client = RpcClient::new(rpc_url);
+    let cli = Cli::parse();
 
-    let token_address_str = "CAeA3EnXgnPrX4YswxKoEowEAdJTZY6AhGGnMNJ3pump";
-    let token_address = Pubkey::from_str(token_address_str)
-        .map_err(|_| error::Error::WrongSizeToken(token_address_str.as_bytes().len()))?;
+    if let Err(e) = run(cli).await {
+        tracing::error!("❌ {e}");
+        std::process::exit(1);
+    }
+}
 
+async fn run(cli: Cli) -> error::Result<()> {
+    let commitment: CommitmentConfig = cli.commitment.into();
     let config = RpcTransactionConfig {
         encoding: Some(UiTransactionEncoding::Json),
-        commitment: Some(CommitmentConfig::confirmed()),
+        commitment: Some(commitment),
         max_supported_transaction_version: Some(0),
     };
 
-    let analysis = run_analysis(&rpc_client, token_address, &config).await;
+    let cache = cli.cache_options().map(|options| TxCache::open(&options)).transpose()?;
+    let cache = cache.as_ref();
 
-    if let Err(e) = analysis {
-        tracing::error!("❌ {e}");
-        std::process::exit(1);
-    } 
+    match &cli.command {
+        Command::Watch { ws_url, backfill, idle_timeout, max_reconnect_delay, max_retries } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let options = WatchOptions {
+                ws_url: cli.ws_url(ws_url.as_ref())?,
+                tx_config: config,
+                retry: RetryPolicy {
+                    max_retries: *max_retries,
+                    ..RetryPolicy::default()
+                },
+                backfill: *backfill,
+                idle_timeout: Duration::from_secs(*idle_timeout),
+                max_reconnect_delay: Duration::from_secs(*max_reconnect_delay),
+            };
+            let watch = watch_launches(rpc_client.as_ref(), cache, &options, |launch| {
+                if let Err(e) = report::print_launch(cli.output, launch) {
+                    tracing::error!("❌ {e}");
+                }
+            });
+            tokio::select! {
+                result = watch => result,
+                _ = tokio::signal::ctrl_c() => Ok(()),
+            }
+        }
+        Command::Scan { mint, options } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let token_address = parse_pubkey(mint)?;
+            let options = options.analysis_options(config);
+            let analysis = run_analysis(rpc_client.as_ref(), cache, token_address, &options).await?;
+            report::print_scan(cli.output, &analysis)
+        }
+        Command::ScanBatch { file, concurrency, sort, ascending, out, options } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let options = options.analysis_options(config);
+            let mints = read_mint_list(file.as_ref())?;
+            // En JSON, une ligne par token au fil de l'eau ; sinon seul le tableau récapitulatif est affiché
+            let batch = scan_batch(rpc_client.as_ref(), cache, mints, &options, *concurrency, |analysis| {
+                if cli.output != OutputFormat::Json {
+                    return;
+                }
+                if let Err(e) = report::print_scan(cli.output, analysis) {
+                    tracing::error!("❌ {e}");
+                }
+            })
+            .await;
+            let mut summary = batch.sorted_frame(sort, !*ascending)?;
+            match out {
+                Some(path) => write_frame(&mut summary, path)?,
+                None => report::print_batch(cli.output, &batch, &summary),
+            }
+            Ok(())
+        }
+        Command::Wallet { pubkey, mint, options } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let wallet = parse_pubkey(pubkey)?;
+            let token_address = parse_pubkey(mint)?;
+            let options = options.analysis_options(config);
+            let analysis = run_analysis(rpc_client.as_ref(), cache, token_address, &options).await?;
+            let ledger = WalletLedger::build(&analysis.trades, analysis.bonding_curve.as_ref().map(CurveState::from));
+            report::print_wallet(cli.output, &wallet, &token_address, &analysis.trades, ledger.get(&wallet))
+        }
+        Command::Pnl { mint, method, sort, top, out, options } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let token_address = parse_pubkey(mint)?;
+            let options = options.analysis_options(config);
+            let analysis = run_analysis(rpc_client.as_ref(), cache, token_address, &options).await?;
+            let method = CostMethod::from(*method);
+            let mut ledger = WalletLedger::build(&analysis.trades, analysis.bonding_curve.as_ref().map(CurveState::from));
+            ledger.sort((*sort).into(), method);
+            match out {
+                Some(path) => write_frame(&mut ledger.frame(method)?, path)?,
+                None => report::print_ledger(cli.output, &ledger, method, *top)?,
+            }
+            Ok(())
+        }
+        Command::Candles { mint, resolution, out, options } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let token_address = parse_pubkey(mint)?;
+            let options = options.analysis_options(config);
+            let analysis = run_analysis(rpc_client.as_ref(), cache, token_address, &options).await?;
+            let resolution = Resolution::from(*resolution);
+            let candles = build_candles(&analysis.trades, resolution);
+            match out {
+                Some(path) => write_frame(&mut candles_frame(&candles)?, path)?,
+                None => report::print_candles(cli.output, resolution, &candles_frame(&candles)?)?,
+            }
+            Ok(())
+        }
+        Command::Trades { mint, out, options } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let token_address = parse_pubkey(mint)?;
+            let options = options.analysis_options(config);
+            let analysis = run_analysis(rpc_client.as_ref(), cache, token_address, &options).await?;
+            write_frame(&mut trades_frame(&analysis.trades)?, out)
+        }
+        Command::Quote { mint, side, sol, tokens, fee_bps, creator_fee_bps } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let token_address = parse_pubkey(mint)?;
+            let amount = quote_amount(*sol, *tokens)?;
+            let fees = Fees {
+                fee_basis_points: *fee_bps,
+                creator_fee_basis_points: *creator_fee_bps,
+            };
+            let curve = fetch_bonding_curve(rpc_client.as_ref(), &token_address)
+                .await
+                .ok_or(error::Error::BondingCurveNotFound(token_address))?;
+            let state = CurveState::from(&curve);
+            let quote = state.quote((*side).into(), amount, fees)?;
+            report::print_quote(cli.output, &token_address, &state, &quote, state.graduation_quote(fees).ok())
+        }
+        Command::Holders { mint, top } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let token_address = parse_pubkey(mint)?;
+            let snapshot = holder_snapshot(rpc_client.as_ref(), token_address).await?;
+            report::print_holders(cli.output, &snapshot, *top)
+        }
+        Command::Creator { address, max_signatures, max_tokens, token_signatures, max_retries } => {
+            let rpc_client = rpc_client(&cli, commitment)?;
+            let address = parse_pubkey(address)?;
+            // Un mint pump.fun renvoie à son créateur, toute autre adresse est prise comme wallet
+            let (creator, current) = match fetch_bonding_curve(rpc_client.as_ref(), &address).await {
+                Some(curve) => (curve.creator, Some(address)),
+                None => (address, None),
+            };
+            let options = CreatorHistoryOptions {
+                tx_config: config,
+                retry: RetryPolicy {
+                    max_retries: *max_retries,
+                    ..RetryPolicy::default()
+                },
+                max_signatures: *max_signatures,
+                max_tokens: *max_tokens,
+                token_signatures: *token_signatures,
+            };
+            let history = creator_history(rpc_client.as_ref(), cache, creator, current, &options).await?;
+            report::print_creator_history(cli.output, &history)
+        }
+    }
+}
+
+fn rpc_client(cli: &Cli, commitment: CommitmentConfig) -> error::Result<Box<dyn RpcBackend>> {
+    if let Some(dir) = &cli.replay {
+        let store = Arc::new(FixtureStore::open(dir)?);
+        return Ok(Box::new(ReplayRpc::new(store, commitment)));
+    }
+    let recorder = cli
+        .record
+        .as_ref()
+        .map(|dir| FixtureStore::open(dir).map(Arc::new))
+        .transpose()?;
 
-    let (token_preflight, decoded_trade, decoded_create) = analysis.unwrap();
-    println!("CreateEvent: {:?}",decoded_create);
-    println!("TokenStats: {:?}", TokenStats::new(&decoded_trade));
+    let urls: Vec<&String> = cli.rpc_urls.iter().filter(|url| !url.trim().is_empty()).collect();
+    if urls.is_empty() {
+        return Err(error::Error::MissingRpcUrl);
+    }
 
-    
-    Ok(())
+    let mut endpoints = Vec::with_capacity(urls.len());
+    for (index, url) in urls.into_iter().enumerate() {
+        let limiter = Arc::new(RateLimiter::new(cli.provider_profile(index)?));
+        let url = url.trim().to_string();
+        let client = match &recorder {
+            Some(store) => RpcClient::new_sender(
+                RecordingSender::new(url, store.clone()),
+                RpcClientConfig::with_commitment(commitment),
+            ),
+            None => RpcClient::new_with_commitment(url, commitment),
+        };
+        endpoints.push(ThrottledRpc::new(client, limiter));
+    }
+    Ok(Box::new(RpcPool::new(endpoints, cli.pool_options())))
 }
@@ -1,7 +1,9 @@
+use serde::Serialize;
 use std::fmt::Display;
 use std::fmt::{Formatter, Result};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Platform {
     PumpFun, 
 }
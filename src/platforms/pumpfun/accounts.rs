@@ -1,7 +1,18 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use serde::Serialize;
+use crate::domain::base58;
+use crate::domain::units::LAMPORTS_PER_SOL;
 use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use crate::platforms::constants::PUMPFUN_PROGRAM_ID;
+use crate::platforms::pumpfun::curve::CurveState;
 
-#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub const BONDING_CURVE_SEED: &[u8] = b"bonding-curve";
+// Réserve réelle de tokens à la création : la courbe est terminée quand elle est vendue
+pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+pub const TOKEN_DECIMALS: u32 = 6;
+
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, Serialize)]
 pub struct BondingCurve {
     pub virtual_token_reserves: u64,
     pub virtual_sol_reserves: u64,
@@ -9,5 +20,32 @@ pub struct BondingCurve {
     pub real_sol_reserves: u64,
     pub token_total_supply: u64,
     pub complete: bool,
+    #[serde(serialize_with = "base58::pubkey")]
     pub creator: Pubkey,
-}
\ No newline at end of file
+}
+
+impl BondingCurve {
+    // PDA de la courbe : seeds ["bonding-curve", mint] sous le programme pump.fun
+    pub fn address(mint: &Pubkey) -> Pubkey {
+        let program = Pubkey::from_str(PUMPFUN_PROGRAM_ID).expect("Wrong address");
+        Pubkey::find_program_address(&[BONDING_CURVE_SEED, mint.as_ref()], &program).0
+    }
+
+    // Prix spot en SOL pour un token entier, déduit des réserves virtuelles
+    pub fn price_sol(&self) -> f64 {
+        CurveState::from(self).spot_price()
+    }
+
+    pub fn market_cap_sol(&self) -> f64 {
+        CurveState::from(self).market_cap_sol()
+    }
+
+    pub fn sol_raised(&self) -> f64 {
+        self.real_sol_reserves as f64 / LAMPORTS_PER_SOL
+    }
+
+    // Part des tokens de la courbe déjà vendue, entre 0 et 1
+    pub fn progress(&self) -> f64 {
+        CurveState::from(self).progress()
+    }
+}
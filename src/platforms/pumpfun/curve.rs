@@ -0,0 +1,468 @@
+use crate::domain::units::{LAMPORTS_PER_SOL, TOKEN_UNIT};
+use crate::platforms::pumpfun::accounts::{BondingCurve, INITIAL_REAL_TOKEN_RESERVES};
+use crate::platforms::pumpfun::events::{CreateEvent, TradeEvent};
+use serde::Serialize;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+// Offre fixe des tokens pump.fun : 1 milliard avec 6 décimales
+pub const TOKEN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum QuoteError {
+    #[error("amount must be greater than zero")]
+    ZeroAmount,
+    #[error("the bonding curve is complete, trading happens on the AMM now")]
+    CurveComplete,
+    #[error("the curve only holds {available} base units, {requested} requested")]
+    InsufficientReserves { requested: u64, available: u64 },
+}
+
+pub type Result<T> = std::result::Result<T, QuoteError>;
+
+// Frais prélevés en plus (achat) ou en moins (vente) du SOL échangé contre la courbe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fees {
+    pub fee_basis_points: u16,
+    pub creator_fee_basis_points: u16,
+}
+
+impl Default for Fees {
+    fn default() -> Self {
+        Self {
+            fee_basis_points: 95,
+            creator_fee_basis_points: 5,
+        }
+    }
+}
+
+impl Fees {
+    pub fn total_basis_points(&self) -> u128 {
+        self.fee_basis_points as u128 + self.creator_fee_basis_points as u128
+    }
+
+    // Le programme arrondit chaque frais au lamport supérieur
+    fn split(&self, sol_amount: u64) -> (u64, u64) {
+        (
+            ceil_div(sol_amount as u128 * self.fee_basis_points as u128, BPS_DENOMINATOR) as u64,
+            ceil_div(sol_amount as u128 * self.creator_fee_basis_points as u128, BPS_DENOMINATOR) as u64,
+        )
+    }
+}
+
+impl From<&TradeEvent> for Fees {
+    fn from(trade: &TradeEvent) -> Self {
+        Self {
+            fee_basis_points: trade.fee_basis_points,
+            creator_fee_basis_points: trade.creator_fee_basis_points,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Side::Buy => write!(f, "BUY"),
+            Side::Sell => write!(f, "SELL"),
+        }
+    }
+}
+
+// Montant d'un trade en unités de base : lamports ou unités de token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Amount {
+    Sol(u64),
+    Tokens(u64),
+}
+
+// Réserves de la courbe, quelle que soit leur provenance (compte, création ou trade)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CurveState {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+}
+
+impl From<&BondingCurve> for CurveState {
+    fn from(curve: &BondingCurve) -> Self {
+        Self {
+            virtual_sol_reserves: curve.virtual_sol_reserves,
+            virtual_token_reserves: curve.virtual_token_reserves,
+            real_sol_reserves: curve.real_sol_reserves,
+            real_token_reserves: curve.real_token_reserves,
+            token_total_supply: curve.token_total_supply,
+            complete: curve.complete,
+        }
+    }
+}
+
+impl From<&CreateEvent> for CurveState {
+    fn from(create: &CreateEvent) -> Self {
+        Self {
+            virtual_sol_reserves: create.virtual_sol_reserves,
+            virtual_token_reserves: create.virtual_token_reserves,
+            real_sol_reserves: 0,
+            real_token_reserves: create.real_token_reserves,
+            token_total_supply: create.token_total_supply,
+            complete: false,
+        }
+    }
+}
+
+// Réserves après le trade, telles que publiées dans l'événement
+impl From<&TradeEvent> for CurveState {
+    fn from(trade: &TradeEvent) -> Self {
+        Self {
+            virtual_sol_reserves: trade.virtual_sol_reserves,
+            virtual_token_reserves: trade.virtual_token_reserves,
+            real_sol_reserves: trade.real_sol_reserves,
+            real_token_reserves: trade.real_token_reserves,
+            token_total_supply: TOKEN_TOTAL_SUPPLY,
+            complete: trade.real_token_reserves == 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Quote {
+    pub side: Side,
+    // SOL échangé contre la courbe, hors frais (lamports)
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub fee: u64,
+    pub creator_fee: u64,
+    // Ce que le trader paie (achat) ou reçoit (vente), frais compris
+    pub total_sol: u64,
+    pub price_before: f64,
+    pub price_after: f64,
+    // Prix moyen d'exécution frais compris, en SOL par token entier
+    pub average_price: f64,
+    // Écart relatif entre prix moyen d'exécution et prix spot avant le trade
+    pub price_impact: f64,
+    pub after: CurveState,
+}
+
+impl CurveState {
+    // Prix spot en SOL pour un token entier
+    pub fn spot_price(&self) -> f64 {
+        if self.virtual_token_reserves == 0 {
+            return 0.0;
+        }
+        (self.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL) / (self.virtual_token_reserves as f64 / TOKEN_UNIT)
+    }
+
+    pub fn market_cap_sol(&self) -> f64 {
+        self.spot_price() * self.token_total_supply as f64 / TOKEN_UNIT
+    }
+
+    // Part des tokens de la courbe déjà vendue, entre 0 et 1
+    pub fn progress(&self) -> f64 {
+        if self.complete {
+            return 1.0;
+        }
+        let sold = INITIAL_REAL_TOKEN_RESERVES.saturating_sub(self.real_token_reserves);
+        sold as f64 / INITIAL_REAL_TOKEN_RESERVES as f64
+    }
+
+    // Achat en dépensant au plus `sol_in` lamports frais compris
+    pub fn buy_exact_sol(&self, sol_in: u64, fees: Fees) -> Result<Quote> {
+        self.ensure_tradable(sol_in)?;
+        // Le prix et chaque frais sont arrondis par excès : on cherche le plus grand nombre de tokens
+        // dont le coût total tient dans le budget, borné par ce que `sol_in` achèterait sans frais
+        // et par les réserves réelles (un budget trop grand achète le reste de la courbe)
+        let mut low = 0;
+        let mut high = ((self.virtual_token_reserves as u128 * sol_in as u128
+            / (self.virtual_sol_reserves as u128 + sol_in as u128)) as u64)
+            .min(self.real_token_reserves);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if self.buy_cost(mid, fees).2 <= sol_in {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        self.buy_exact_tokens(low, fees)
+    }
+
+    // Achat d'un nombre exact de tokens (unités de base)
+    pub fn buy_exact_tokens(&self, tokens: u64, fees: Fees) -> Result<Quote> {
+        self.ensure_tradable(tokens)?;
+        if tokens > self.real_token_reserves {
+            return Err(QuoteError::InsufficientReserves {
+                requested: tokens,
+                available: self.real_token_reserves,
+            });
+        }
+        let (sol, fee_split, total) = self.buy_cost(tokens, fees);
+        let after = CurveState {
+            virtual_sol_reserves: self.virtual_sol_reserves + sol,
+            virtual_token_reserves: self.virtual_token_reserves - tokens,
+            real_sol_reserves: self.real_sol_reserves + sol,
+            real_token_reserves: self.real_token_reserves - tokens,
+            complete: self.real_token_reserves == tokens,
+            ..*self
+        };
+        Ok(self.build_quote(Side::Buy, sol, tokens, fee_split, total, after))
+    }
+
+    // SOL échangé contre la courbe, frais, et total payé pour `tokens` (au plus les réserves réelles)
+    fn buy_cost(&self, tokens: u64, fees: Fees) -> (u64, (u64, u64), u64) {
+        let sol = ceil_div(
+            self.virtual_sol_reserves as u128 * tokens as u128,
+            (self.virtual_token_reserves - tokens) as u128,
+        ) as u64;
+        let (fee, creator_fee) = fees.split(sol);
+        (sol, (fee, creator_fee), sol + fee + creator_fee)
+    }
+
+    // Vente d'un nombre exact de tokens
+    pub fn sell_exact_tokens(&self, tokens: u64, fees: Fees) -> Result<Quote> {
+        self.ensure_tradable(tokens)?;
+        let sol = (self.virtual_sol_reserves as u128 * tokens as u128
+            / (self.virtual_token_reserves as u128 + tokens as u128)) as u64;
+        if sol > self.real_sol_reserves {
+            return Err(QuoteError::InsufficientReserves {
+                requested: sol,
+                available: self.real_sol_reserves,
+            });
+        }
+        let (fee, creator_fee) = fees.split(sol);
+        let after = CurveState {
+            virtual_sol_reserves: self.virtual_sol_reserves - sol,
+            virtual_token_reserves: self.virtual_token_reserves + tokens,
+            real_sol_reserves: self.real_sol_reserves - sol,
+            real_token_reserves: self.real_token_reserves + tokens,
+            ..*self
+        };
+        let total = sol.saturating_sub(fee + creator_fee);
+        Ok(self.build_quote(Side::Sell, sol, tokens, (fee, creator_fee), total, after))
+    }
+
+    // Vente du nombre de tokens nécessaire pour recevoir `sol_out` lamports nets de frais
+    pub fn sell_for_sol(&self, sol_out: u64, fees: Fees) -> Result<Quote> {
+        self.ensure_tradable(sol_out)?;
+        let gross = ceil_div(
+            sol_out as u128 * BPS_DENOMINATOR,
+            BPS_DENOMINATOR.saturating_sub(fees.total_basis_points()).max(1),
+        );
+        if gross >= self.real_sol_reserves as u128 {
+            return Err(QuoteError::InsufficientReserves {
+                requested: gross as u64,
+                available: self.real_sol_reserves,
+            });
+        }
+        let tokens = ceil_div(
+            gross * self.virtual_token_reserves as u128,
+            self.virtual_sol_reserves as u128 - gross,
+        ) as u64;
+        self.sell_exact_tokens(tokens, fees)
+    }
+
+    pub fn quote(&self, side: Side, amount: Amount, fees: Fees) -> Result<Quote> {
+        match (side, amount) {
+            (Side::Buy, Amount::Sol(sol)) => self.buy_exact_sol(sol, fees),
+            (Side::Buy, Amount::Tokens(tokens)) => self.buy_exact_tokens(tokens, fees),
+            (Side::Sell, Amount::Sol(sol)) => self.sell_for_sol(sol, fees),
+            (Side::Sell, Amount::Tokens(tokens)) => self.sell_exact_tokens(tokens, fees),
+        }
+    }
+
+    // Coût, frais compris, pour acheter tout ce qui reste sur la courbe
+    pub fn graduation_quote(&self, fees: Fees) -> Result<Quote> {
+        self.buy_exact_tokens(self.real_token_reserves, fees)
+    }
+
+    fn ensure_tradable(&self, amount: u64) -> Result<()> {
+        if self.complete {
+            return Err(QuoteError::CurveComplete);
+        }
+        if amount == 0 {
+            return Err(QuoteError::ZeroAmount);
+        }
+        Ok(())
+    }
+
+    fn build_quote(
+        &self,
+        side: Side,
+        sol_amount: u64,
+        token_amount: u64,
+        (fee, creator_fee): (u64, u64),
+        total_sol: u64,
+        after: CurveState,
+    ) -> Quote {
+        let price_before = self.spot_price();
+        let average_price = (total_sol as f64 / LAMPORTS_PER_SOL) / (token_amount as f64 / TOKEN_UNIT);
+        let price_impact = if price_before > 0.0 { average_price / price_before - 1.0 } else { 0.0 };
+        Quote {
+            side,
+            sol_amount,
+            token_amount,
+            fee,
+            creator_fee,
+            total_sol,
+            price_before,
+            price_after: after.spot_price(),
+            average_price,
+            price_impact,
+            after,
+        }
+    }
+}
+
+fn ceil_div(numerator: u128, denominator: u128) -> u128 {
+    numerator.div_ceil(denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Courbe pump.fun fraîchement créée
+    fn fresh() -> CurveState {
+        CurveState {
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: INITIAL_REAL_TOKEN_RESERVES,
+            token_total_supply: TOKEN_TOTAL_SUPPLY,
+            complete: false,
+        }
+    }
+
+    // Courbe après un achat, pour pouvoir vendre
+    fn traded() -> CurveState {
+        fresh().buy_exact_sol(5_000_000_000, Fees::default()).unwrap().after
+    }
+
+    #[test]
+    fn buy_exact_sol_never_exceeds_the_budget() {
+        let curve = traded();
+        for sol_in in [1_000, 999_999, 1_000_000_000, 1_234_567_891, 7_000_000_003] {
+            let quote = curve.buy_exact_sol(sol_in, Fees::default()).unwrap();
+            assert!(quote.total_sol <= sol_in, "{} > {sol_in}", quote.total_sol);
+            // Un token de plus dépasserait le budget
+            let more = curve.buy_exact_tokens(quote.token_amount + 1, Fees::default()).unwrap();
+            assert!(more.total_sol > sol_in);
+        }
+    }
+
+    #[test]
+    fn buy_exact_sol_spends_an_exact_budget_entirely() {
+        let curve = fresh();
+        let target = curve.buy_exact_tokens(100_000_000_000, Fees::default()).unwrap();
+
+        let quote = curve.buy_exact_sol(target.total_sol, Fees::default()).unwrap();
+
+        assert_eq!(quote.total_sol, target.total_sol);
+        assert!(quote.token_amount >= target.token_amount);
+    }
+
+    #[test]
+    fn buy_exact_sol_drains_the_curve_when_the_budget_is_larger() {
+        let curve = traded();
+        let graduation = curve.graduation_quote(Fees::default()).unwrap();
+
+        let quote = curve.buy_exact_sol(graduation.total_sol * 2, Fees::default()).unwrap();
+
+        assert_eq!(quote.token_amount, curve.real_token_reserves);
+        assert_eq!(quote.total_sol, graduation.total_sol);
+        assert!(quote.after.complete);
+        assert_eq!(quote.after.real_token_reserves, 0);
+        assert_eq!(quote.after.progress(), 1.0);
+    }
+
+    #[test]
+    fn buy_exact_tokens_rounds_cost_and_fees_up() {
+        let curve = fresh();
+        let tokens = 1_000_000;
+
+        let quote = curve.buy_exact_tokens(tokens, Fees::default()).unwrap();
+
+        // 30e9 * 1e6 / (1.073e15 - 1e6) = 27.95… lamports
+        assert_eq!(quote.sol_amount, 28);
+        assert_eq!((quote.fee, quote.creator_fee), (1, 1));
+        assert_eq!(quote.total_sol, 30);
+        assert_eq!(quote.after.virtual_token_reserves, curve.virtual_token_reserves - tokens);
+        assert_eq!(quote.after.real_sol_reserves, 28);
+        // k = x * y ne diminue jamais
+        let k_before = curve.virtual_sol_reserves as u128 * curve.virtual_token_reserves as u128;
+        let k_after = quote.after.virtual_sol_reserves as u128 * quote.after.virtual_token_reserves as u128;
+        assert!(k_after >= k_before);
+    }
+
+    #[test]
+    fn buy_exact_tokens_refuses_more_than_the_real_reserves() {
+        let curve = traded();
+
+        let result = curve.buy_exact_tokens(curve.real_token_reserves + 1, Fees::default());
+
+        assert_eq!(
+            result.unwrap_err(),
+            QuoteError::InsufficientReserves {
+                requested: curve.real_token_reserves + 1,
+                available: curve.real_token_reserves,
+            }
+        );
+        assert!(curve.buy_exact_tokens(curve.real_token_reserves, Fees::default()).unwrap().after.complete);
+    }
+
+    #[test]
+    fn sell_exact_tokens_pays_out_net_of_fees() {
+        let curve = traded();
+        let tokens = 10_000_000_000;
+
+        let quote = curve.sell_exact_tokens(tokens, Fees::default()).unwrap();
+
+        assert_eq!(quote.total_sol, quote.sol_amount - quote.fee - quote.creator_fee);
+        assert_eq!(quote.after.real_sol_reserves, curve.real_sol_reserves - quote.sol_amount);
+        assert!(quote.price_after < quote.price_before);
+        assert!(quote.price_impact < 0.0);
+    }
+
+    #[test]
+    fn selling_back_everything_bought_returns_less_than_was_paid() {
+        let curve = fresh();
+        let buy = curve.buy_exact_sol(1_000_000_000, Fees::default()).unwrap();
+
+        let sell = buy.after.sell_exact_tokens(buy.token_amount, Fees::default()).unwrap();
+
+        assert!(sell.total_sol < buy.total_sol);
+        assert!(sell.sol_amount <= buy.sol_amount);
+        assert!(sell.after.real_sol_reserves <= buy.after.real_sol_reserves);
+    }
+
+    #[test]
+    fn sell_for_sol_receives_at_least_the_requested_amount() {
+        let curve = traded();
+
+        let quote = curve.sell_for_sol(1_000_000_000, Fees::default()).unwrap();
+
+        assert!(quote.total_sol >= 1_000_000_000);
+        assert!(curve.sell_for_sol(curve.real_sol_reserves, Fees::default()).is_err());
+    }
+
+    #[test]
+    fn quotes_reject_zero_amounts_and_complete_curves() {
+        let curve = fresh();
+        assert_eq!(curve.buy_exact_sol(0, Fees::default()).unwrap_err(), QuoteError::ZeroAmount);
+        assert_eq!(curve.buy_exact_tokens(0, Fees::default()).unwrap_err(), QuoteError::ZeroAmount);
+        assert_eq!(curve.sell_exact_tokens(0, Fees::default()).unwrap_err(), QuoteError::ZeroAmount);
+
+        let complete = CurveState { complete: true, ..traded() };
+        assert_eq!(
+            complete.buy_exact_sol(1_000_000_000, Fees::default()).unwrap_err(),
+            QuoteError::CurveComplete
+        );
+    }
+}
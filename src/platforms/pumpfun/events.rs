@@ -1,14 +1,24 @@
+use crate::domain::base58;
+use crate::domain::decoder::event::EventEnvelope;
 use borsh::{BorshDeserialize, BorshSerialize};
+use serde::Serialize;
 use solana_sdk::pubkey::Pubkey;
 
-#[derive(Debug, BorshDeserialize, BorshSerialize)]
+pub type CreateEnvelope = EventEnvelope<CreateEvent>;
+pub type TradeEnvelope = EventEnvelope<TradeEvent>;
+
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, Serialize)]
 pub struct CreateEvent {
     pub name: String,
     pub symbol: String,
     pub uri: String,
+    #[serde(serialize_with = "base58::pubkey")]
     pub mint: Pubkey,
+    #[serde(serialize_with = "base58::pubkey")]
     pub bonding_curve: Pubkey,
+    #[serde(serialize_with = "base58::pubkey")]
     pub user: Pubkey,
+    #[serde(serialize_with = "base58::pubkey")]
     pub creator: Pubkey,
     pub timestamp: u64,
     pub virtual_token_reserves: u64,
@@ -43,22 +53,27 @@ pub struct TradeEventWire {
 }
 
 
-#[derive(Debug)]
+// Signature, slot et position sont portés par l'enveloppe (`TradeEnvelope`).
+// Mêmes champs, dans le même ordre, que `TradeEventWire`
+#[derive(Debug, Clone, BorshSerialize, Serialize)]
 pub struct TradeEvent {
-    pub signature: String, 
+    #[serde(serialize_with = "base58::pubkey")]
     pub mint: Pubkey,
     pub sol_amount: u64,
     pub token_amount: u64,
     pub is_buy: bool,
+    #[serde(serialize_with = "base58::pubkey")]
     pub user: Pubkey,
     pub timestamp: u64,
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
     pub real_sol_reserves: u64,
     pub real_token_reserves: u64,
+    #[serde(serialize_with = "base58::pubkey")]
     pub fee_recipient: Pubkey,
     pub fee_basis_points: u16,
     pub fee: u64,
+    #[serde(serialize_with = "base58::pubkey")]
     pub creator: Pubkey,
     pub creator_fee_basis_points: u16,
     pub creator_fee: u64,
@@ -69,10 +84,9 @@ pub struct TradeEvent {
     pub last_update_timestamp: u64,
 }
 
-impl From<(String, TradeEventWire)> for TradeEvent {
-    fn from((signature, w): (String, TradeEventWire)) -> Self {
+impl From<TradeEventWire> for TradeEvent {
+    fn from(w: TradeEventWire) -> Self {
         TradeEvent {
-            signature,
             mint: w.mint,
             sol_amount: w.sol_amount,
             token_amount: w.token_amount,
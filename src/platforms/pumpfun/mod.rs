@@ -1,3 +1,7 @@
 pub mod events;
 pub mod pumpfun;
-pub mod accounts;
\ No newline at end of file
+pub mod accounts;
+pub mod curve;
+pub mod validation;
+#[cfg(test)]
+pub mod sample;
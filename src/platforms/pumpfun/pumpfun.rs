@@ -55,7 +55,7 @@ impl EventDecoder for PumpFun {
            
     }
 
-    fn decode_trade(&self, signature: &String, mut payload: &[u8]) -> Result<Self::Trade> {
+    fn decode_trade(&self, mut payload: &[u8]) -> Result<Self::Trade> {
         if payload.len() < 8 {
             return Err(DecodeError::ShortBuffer("discriminator"));
         }
@@ -63,7 +63,7 @@ impl EventDecoder for PumpFun {
 
         let wire: TradeEventWire = TradeEventWire::deserialize_reader(&mut payload)?;
 
-        Ok((signature.to_string(), wire).into())
+        Ok(wire.into())
 
     }
 
@@ -83,7 +83,7 @@ impl AccountDecoder for PumpFun {
 
         match *owner {
             x if x == pump_program => Some(AccountKind::BondingCurve), 
-            ID => Some(AccountKind::Mint), 
+            ID | spl_token_2022::ID => Some(AccountKind::Mint), 
             _ => {
                 None
             }
@@ -0,0 +1,298 @@
+use crate::domain::decoder::event::EventEnvelope;
+use crate::infra::memory_rpc::MemoryRpc;
+use crate::platforms::constants::PUMPFUN_PROGRAM_ID;
+use crate::platforms::pumpfun::accounts::{BondingCurve, INITIAL_REAL_TOKEN_RESERVES};
+use crate::platforms::pumpfun::curve::{CurveState, Fees, Quote, Side, TOKEN_TOTAL_SUPPLY};
+use crate::platforms::pumpfun::events::{CreateEvent, TradeEnvelope, TradeEvent};
+use crate::platforms::pumpfun::pumpfun::{CREATE_DISCRIMINATOR, TRADE_DISCRIMINATOR};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::json;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::account::Account;
+use solana_sdk::program_option::COption;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+use std::str::FromStr;
+
+// Lancement pump.fun synthétique pour les tests : événements cohérents avec la courbe,
+// transactions au format JSON de getTransaction
+pub const CREATION_SLOT: u64 = 340_000_000;
+pub const CREATION_TIME: i64 = 1_750_000_000;
+pub const VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000;
+pub const VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+
+pub fn key(seed: u8) -> Pubkey {
+    Pubkey::new_from_array([seed; 32])
+}
+
+pub fn signature(seed: u8) -> Signature {
+    Signature::from([seed; 64])
+}
+
+pub struct SampleTx {
+    pub signature: Signature,
+    pub slot: u64,
+    pub block_time: i64,
+    pub signer: Pubkey,
+    pub creates: Vec<CreateEvent>,
+    pub trades: Vec<TradeEvent>,
+}
+
+impl SampleTx {
+    pub fn logs(&self) -> Vec<String> {
+        let mut logs = vec![format!("Program {PUMPFUN_PROGRAM_ID} invoke [1]")];
+        for create in &self.creates {
+            logs.push("Program log: Instruction: Create".to_string());
+            logs.push(program_data(CREATE_DISCRIMINATOR, &borsh::to_vec(create).unwrap()));
+        }
+        for trade in &self.trades {
+            logs.push(format!("Program log: Instruction: {}", if trade.is_buy { "Buy" } else { "Sell" }));
+            logs.push(program_data(TRADE_DISCRIMINATOR, &borsh::to_vec(trade).unwrap()));
+        }
+        logs.push(format!("Program {PUMPFUN_PROGRAM_ID} success"));
+        logs
+    }
+
+    pub fn status(&self) -> RpcConfirmedTransactionStatusWithSignature {
+        RpcConfirmedTransactionStatusWithSignature {
+            signature: self.signature.to_string(),
+            slot: self.slot,
+            err: None,
+            memo: None,
+            block_time: Some(self.block_time),
+            confirmation_status: None,
+        }
+    }
+
+    pub fn encoded(&self) -> EncodedConfirmedTransactionWithStatusMeta {
+        let mint = self
+            .creates
+            .first()
+            .map(|c| c.mint)
+            .or(self.trades.first().map(|t| t.mint))
+            .unwrap_or_default();
+        serde_json::from_value(json!({
+            "slot": self.slot,
+            "blockTime": self.block_time,
+            "transaction": {
+                "signatures": [self.signature.to_string()],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 1,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 1,
+                    },
+                    "accountKeys": [self.signer.to_string(), mint.to_string(), PUMPFUN_PROGRAM_ID],
+                    "recentBlockhash": "11111111111111111111111111111111",
+                    "instructions": [],
+                },
+            },
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5000,
+                "preBalances": [0, 0, 0],
+                "postBalances": [0, 0, 0],
+                "innerInstructions": [],
+                "logMessages": self.logs(),
+                "preTokenBalances": [],
+                "postTokenBalances": [],
+                "rewards": [],
+            },
+        }))
+        .expect("sample transaction matches the getTransaction schema")
+    }
+}
+
+fn program_data(discriminator: [u8; 8], payload: &[u8]) -> String {
+    let mut blob = discriminator.to_vec();
+    blob.extend_from_slice(payload);
+    format!("Program data: {}", STANDARD.encode(blob))
+}
+
+// Trade publié par le programme pour une cotation donnée
+pub fn trade_event(mint: Pubkey, user: Pubkey, creator: Pubkey, quote: &Quote, timestamp: i64) -> TradeEvent {
+    TradeEvent {
+        mint,
+        sol_amount: quote.sol_amount,
+        token_amount: quote.token_amount,
+        is_buy: quote.side == Side::Buy,
+        user,
+        timestamp: timestamp as u64,
+        virtual_sol_reserves: quote.after.virtual_sol_reserves,
+        virtual_token_reserves: quote.after.virtual_token_reserves,
+        real_sol_reserves: quote.after.real_sol_reserves,
+        real_token_reserves: quote.after.real_token_reserves,
+        fee_recipient: key(99),
+        fee_basis_points: Fees::default().fee_basis_points,
+        fee: quote.fee,
+        creator,
+        creator_fee_basis_points: Fees::default().creator_fee_basis_points,
+        creator_fee: quote.creator_fee,
+        track_volume: false,
+        total_unclaimed_tokens: 0,
+        total_claimed_tokens: 0,
+        current_sol_volume: 0,
+        last_update_timestamp: timestamp as u64,
+    }
+}
+
+// Création avec achat du dev, deux acheteurs dont un dans le slot de création, une revente partielle
+pub struct SampleLaunch {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub buyers: [Pubkey; 3],
+    pub curve: CurveState,
+    pub transactions: Vec<SampleTx>,
+}
+
+impl SampleLaunch {
+    pub fn new() -> Self {
+        let mint = key(1);
+        let creator = key(2);
+        let buyers = [key(3), key(4), key(5)];
+        let create = CreateEvent {
+            name: "Sample".to_string(),
+            symbol: "SMPL".to_string(),
+            uri: "https://example.com/sample.json".to_string(),
+            mint,
+            bonding_curve: BondingCurve::address(&mint),
+            user: creator,
+            creator,
+            timestamp: CREATION_TIME as u64,
+            virtual_token_reserves: VIRTUAL_TOKEN_RESERVES,
+            virtual_sol_reserves: VIRTUAL_SOL_RESERVES,
+            real_token_reserves: INITIAL_REAL_TOKEN_RESERVES,
+            token_total_supply: TOKEN_TOTAL_SUPPLY,
+        };
+        let mut launch = Self {
+            mint,
+            creator,
+            buyers,
+            curve: CurveState::from(&create),
+            transactions: Vec::new(),
+        };
+
+        let dev_buy = launch.trade(creator, Side::Buy, 500_000_000, 0);
+        launch.push(10, creator, 0, vec![create], vec![dev_buy]);
+        let snipe = launch.trade(buyers[0], Side::Buy, 1_000_000_000, 0);
+        launch.push(11, buyers[0], 0, Vec::new(), vec![snipe]);
+        let early = launch.trade(buyers[1], Side::Buy, 500_000_000, 1);
+        launch.push(12, buyers[1], 1, Vec::new(), vec![early]);
+        let half = launch.transactions[1].trades[0].token_amount / 2;
+        let exit = launch.trade(buyers[0], Side::Sell, half, 3);
+        launch.push(13, buyers[0], 3, Vec::new(), vec![exit]);
+        let late = launch.trade(buyers[2], Side::Buy, 2_000_000_000, 5);
+        launch.push(14, buyers[2], 5, Vec::new(), vec![late]);
+        launch
+    }
+
+    // Achat en SOL frais compris, vente en tokens ; les réserves avancent avec chaque trade
+    fn trade(&mut self, user: Pubkey, side: Side, amount: u64, offset: u64) -> TradeEvent {
+        let quote = match side {
+            Side::Buy => self.curve.buy_exact_sol(amount, Fees::default()),
+            Side::Sell => self.curve.sell_exact_tokens(amount, Fees::default()),
+        }
+        .expect("sample trade fits the curve");
+        self.curve = quote.after;
+        trade_event(self.mint, user, self.creator, &quote, CREATION_TIME + offset as i64)
+    }
+
+    fn push(&mut self, seed: u8, signer: Pubkey, offset: u64, creates: Vec<CreateEvent>, trades: Vec<TradeEvent>) {
+        self.transactions.push(SampleTx {
+            signature: signature(seed),
+            slot: CREATION_SLOT + offset,
+            block_time: CREATION_TIME + offset as i64,
+            signer,
+            creates,
+            trades,
+        });
+    }
+
+    pub fn create(&self) -> &CreateEvent {
+        &self.transactions[0].creates[0]
+    }
+
+    // Trades tels que le décodage les enveloppe, dans l'ordre chronologique
+    pub fn trade_envelopes(&self) -> Vec<TradeEnvelope> {
+        self.transactions
+            .iter()
+            .flat_map(|tx| {
+                tx.trades.iter().enumerate().map(|(index, trade)| EventEnvelope {
+                    signature: tx.signature.to_string(),
+                    slot: tx.slot,
+                    block_time: Some(tx.block_time),
+                    tx_index: None,
+                    event_index: (tx.creates.len() + index) as u32,
+                    event: trade.clone(),
+                })
+            })
+            .collect()
+    }
+
+    // Du plus récent au plus ancien, comme getSignaturesForAddress
+    pub fn statuses(&self) -> Vec<RpcConfirmedTransactionStatusWithSignature> {
+        self.transactions.iter().rev().map(SampleTx::status).collect()
+    }
+
+    pub fn mint_account(&self) -> Account {
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: TOKEN_TOTAL_SUPPLY,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(mint, &mut data).unwrap();
+        Account {
+            lamports: 1_461_600,
+            data,
+            owner: spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    // Compte de la courbe après le dernier trade, précédé du discriminant Anchor
+    pub fn curve_account(&self) -> Account {
+        let curve = BondingCurve {
+            virtual_token_reserves: self.curve.virtual_token_reserves,
+            virtual_sol_reserves: self.curve.virtual_sol_reserves,
+            real_token_reserves: self.curve.real_token_reserves,
+            real_sol_reserves: self.curve.real_sol_reserves,
+            token_total_supply: self.curve.token_total_supply,
+            complete: self.curve.complete,
+            creator: self.creator,
+        };
+        let mut data = vec![0; 8];
+        data.extend(borsh::to_vec(&curve).unwrap());
+        Account {
+            lamports: self.curve.real_sol_reserves,
+            data,
+            owner: Pubkey::from_str(PUMPFUN_PROGRAM_ID).unwrap(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    // Nœud en mémoire qui connaît le mint, la courbe, l'historique et toutes les transactions
+    pub fn rpc(&self) -> MemoryRpc {
+        self.transactions
+            .iter()
+            .fold(self.rpc_without_transactions(), |rpc, tx| {
+                rpc.with_transaction(&tx.signature.to_string(), tx.encoded())
+            })
+    }
+
+    // Mêmes comptes et historique, transactions à déposer par le test
+    pub fn rpc_without_transactions(&self) -> MemoryRpc {
+        MemoryRpc::new()
+            .with_account(self.mint, self.mint_account())
+            .with_account(BondingCurve::address(&self.mint), self.curve_account())
+            .with_signatures(self.mint, self.statuses())
+    }
+}
@@ -0,0 +1,283 @@
+use crate::platforms::pumpfun::events::{CreateEvent, TradeEnvelope, TradeEvent};
+use serde::Serialize;
+use std::fmt::Display;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+// Le programme arrondit au lamport ou à l'unité de token près
+const ROUNDING_TOLERANCE: u128 = 2;
+
+// Réserves virtuelles et réelles à un instant donné de la courbe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reserves {
+    pub virtual_sol: u64,
+    pub virtual_token: u64,
+    pub real_sol: u64,
+    pub real_token: u64,
+}
+
+impl Reserves {
+    fn after(trade: &TradeEvent) -> Self {
+        Self {
+            virtual_sol: trade.virtual_sol_reserves,
+            virtual_token: trade.virtual_token_reserves,
+            real_sol: trade.real_sol_reserves,
+            real_token: trade.real_token_reserves,
+        }
+    }
+
+    // Les événements publient les réserves après le trade : on remonte à l'état d'avant
+    fn before(trade: &TradeEvent) -> Option<Self> {
+        let after = Self::after(trade);
+        let (sol, token) = (trade.sol_amount, trade.token_amount);
+        Some(if trade.is_buy {
+            Self {
+                virtual_sol: after.virtual_sol.checked_sub(sol)?,
+                virtual_token: after.virtual_token.checked_add(token)?,
+                real_sol: after.real_sol.checked_sub(sol)?,
+                real_token: after.real_token.checked_add(token)?,
+            }
+        } else {
+            Self {
+                virtual_sol: after.virtual_sol.checked_add(sol)?,
+                virtual_token: after.virtual_token.checked_sub(token)?,
+                real_sol: after.real_sol.checked_add(sol)?,
+                real_token: after.real_token.checked_sub(token)?,
+            }
+        })
+    }
+
+    fn k(&self) -> u128 {
+        self.virtual_sol as u128 * self.virtual_token as u128
+    }
+}
+
+impl From<&CreateEvent> for Reserves {
+    fn from(create: &CreateEvent) -> Self {
+        Self {
+            virtual_sol: create.virtual_sol_reserves,
+            virtual_token: create.virtual_token_reserves,
+            real_sol: 0,
+            real_token: create.real_token_reserves,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchKind {
+    // Montants incompatibles avec x·y = k entre réserves avant et après
+    Invariant,
+    Fee,
+    CreatorFee,
+}
+
+impl Display for MismatchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MismatchKind::Invariant => write!(f, "amounts do not match reserve deltas"),
+            MismatchKind::Fee => write!(f, "fee does not match fee_basis_points"),
+            MismatchKind::CreatorFee => write!(f, "creator fee does not match creator_fee_basis_points"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Mismatch {
+    pub signature: String,
+    pub kind: MismatchKind,
+    pub expected: u128,
+    pub actual: u128,
+}
+
+// Saut de réserves entre deux trades consécutifs : des trades manquent entre les deux
+#[derive(Debug, Clone, Serialize)]
+pub struct ReserveGap {
+    // `None` quand le saut est mesuré depuis l'événement de création
+    pub after_signature: Option<String>,
+    pub before_signature: String,
+    // Variation des réserves virtuelles non expliquée par les trades décodés
+    pub missing_sol: i128,
+    pub missing_tokens: i128,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub trades_checked: usize,
+    pub gaps: Vec<ReserveGap>,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl ValidationReport {
+    pub fn is_consistent(&self) -> bool {
+        self.gaps.is_empty() && self.mismatches.is_empty()
+    }
+}
+
+// Rejoue les trades dans l'ordre chronologique à travers l'invariant de la courbe
+pub fn validate_trades(create: Option<&CreateEvent>, trades: &[TradeEnvelope]) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut previous: Option<(Option<&str>, Reserves)> = create.map(|c| (None, Reserves::from(c)));
+
+    for trade in trades {
+        report.trades_checked += 1;
+        let after = Reserves::after(trade);
+
+        match Reserves::before(trade) {
+            Some(before) => {
+                if !invariant_holds(&before, &after) {
+                    report.mismatches.push(Mismatch {
+                        signature: trade.signature.clone(),
+                        kind: MismatchKind::Invariant,
+                        expected: before.k(),
+                        actual: after.k(),
+                    });
+                } else if let Some((signature, expected)) = previous.filter(|(_, prev)| *prev != before) {
+                    report.gaps.push(ReserveGap {
+                        after_signature: signature.map(str::to_string),
+                        before_signature: trade.signature.clone(),
+                        missing_sol: before.virtual_sol as i128 - expected.virtual_sol as i128,
+                        missing_tokens: before.virtual_token as i128 - expected.virtual_token as i128,
+                    });
+                }
+            }
+            // Montants plus grands que les réserves : l'événement est incohérent
+            None => report.mismatches.push(Mismatch {
+                signature: trade.signature.clone(),
+                kind: MismatchKind::Invariant,
+                expected: 0,
+                actual: after.k(),
+            }),
+        }
+
+        check_fee(&mut report, trade, trade.fee_basis_points, trade.fee, MismatchKind::Fee);
+        check_fee(
+            &mut report,
+            trade,
+            trade.creator_fee_basis_points,
+            trade.creator_fee,
+            MismatchKind::CreatorFee,
+        );
+
+        previous = Some((Some(trade.signature.as_str()), after));
+    }
+
+    if !report.is_consistent() {
+        tracing::warn!(
+            gaps = report.gaps.len(),
+            mismatches = report.mismatches.len(),
+            "⚠️ séquence de trades incohérente avec la courbe"
+        );
+    }
+    report
+}
+
+// Les arrondis favorisent toujours la courbe : k ne baisse jamais et n'augmente que de quelques unités
+fn invariant_holds(before: &Reserves, after: &Reserves) -> bool {
+    let (k_before, k_after) = (before.k(), after.k());
+    let slack = ROUNDING_TOLERANCE * (after.virtual_sol as u128 + after.virtual_token as u128);
+    k_after >= k_before && k_after - k_before <= slack
+}
+
+fn check_fee(report: &mut ValidationReport, trade: &TradeEnvelope, basis_points: u16, actual: u64, kind: MismatchKind) {
+    let expected = (trade.sol_amount as u128 * basis_points as u128).div_ceil(BPS_DENOMINATOR);
+    if expected.abs_diff(actual as u128) > 1 {
+        report.mismatches.push(Mismatch {
+            signature: trade.signature.clone(),
+            kind,
+            expected,
+            actual: actual as u128,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::sample::SampleLaunch;
+
+    #[test]
+    fn trades_replayed_from_the_curve_are_consistent() {
+        let launch = SampleLaunch::new();
+        let trades = launch.trade_envelopes();
+
+        let report = validate_trades(Some(launch.create()), &trades);
+
+        assert_eq!(report.trades_checked, trades.len());
+        assert!(report.is_consistent(), "{report:?}");
+    }
+
+    #[test]
+    fn a_missing_trade_shows_up_as_a_reserve_gap() {
+        let launch = SampleLaunch::new();
+        let mut trades = launch.trade_envelopes();
+        let missing = trades.remove(2);
+
+        let report = validate_trades(Some(launch.create()), &trades);
+
+        assert!(report.mismatches.is_empty());
+        assert_eq!(report.gaps.len(), 1);
+        let gap = &report.gaps[0];
+        assert_eq!(gap.after_signature.as_deref(), Some(trades[1].signature.as_str()));
+        assert_eq!(gap.before_signature, trades[2].signature);
+        assert_eq!(gap.missing_sol, missing.sol_amount as i128);
+        assert_eq!(gap.missing_tokens, -(missing.token_amount as i128));
+    }
+
+    #[test]
+    fn a_gap_before_the_first_trade_is_measured_from_the_creation() {
+        let launch = SampleLaunch::new();
+        let trades = launch.trade_envelopes();
+
+        let report = validate_trades(Some(launch.create()), &trades[1..]);
+
+        assert_eq!(report.gaps.len(), 1);
+        assert_eq!(report.gaps[0].after_signature, None);
+        assert_eq!(report.gaps[0].missing_sol, trades[0].sol_amount as i128);
+    }
+
+    #[test]
+    fn amounts_that_break_the_invariant_are_mismatches() {
+        let launch = SampleLaunch::new();
+        let mut trades = launch.trade_envelopes();
+        // Reçoit 1 % de tokens en plus que ce que les réserves ont cédé
+        trades[1].event.token_amount += trades[1].token_amount / 100;
+
+        let report = validate_trades(Some(launch.create()), &trades);
+
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].kind, MismatchKind::Invariant);
+        assert_eq!(report.mismatches[0].signature, trades[1].signature);
+    }
+
+    #[test]
+    fn amounts_larger_than_the_reserves_are_mismatches() {
+        let launch = SampleLaunch::new();
+        let mut trades = launch.trade_envelopes();
+        trades[0].event.sol_amount = trades[0].real_sol_reserves + 1;
+
+        let report = validate_trades(Some(launch.create()), &trades);
+
+        let first = &report.mismatches[0];
+        assert_eq!((first.kind, first.expected), (MismatchKind::Invariant, 0));
+    }
+
+    #[test]
+    fn fees_must_match_their_basis_points() {
+        let launch = SampleLaunch::new();
+        let mut trades = launch.trade_envelopes();
+        trades[2].event.fee += 10;
+        trades[3].event.creator_fee = 0;
+
+        let report = validate_trades(Some(launch.create()), &trades);
+
+        let kinds: Vec<(MismatchKind, &str)> =
+            report.mismatches.iter().map(|m| (m.kind, m.signature.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (MismatchKind::Fee, trades[2].signature.as_str()),
+                (MismatchKind::CreatorFee, trades[3].signature.as_str()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,686 @@
+use crate::cli::OutputFormat;
+use crate::domain::analysis::{TokenAnalysis, TokenPreflight};
+use crate::domain::base58;
+use crate::domain::batch::BatchReport;
+use crate::domain::bundles::BundleReport;
+use crate::domain::dev::{DevReport, DevTrade};
+use crate::domain::holders::{Holder, HolderSnapshot};
+use crate::domain::candles::Resolution;
+use crate::domain::creator_history::CreatorHistory;
+use crate::domain::ledger::{CostMethod, Pnl, WalletLedger, WalletPosition};
+use crate::domain::snipers::{LaunchReference, SniperReport};
+use crate::error;
+use polars::prelude::{DataFrame, JsonFormat, JsonWriter, SerWriter};
+use serde::Serialize;
+use crate::domain::token_stats::TokenStats;
+use crate::domain::units::{LAMPORTS_PER_SOL, TOKEN_UNIT};
+use crate::platforms::pumpfun::accounts::BondingCurve;
+use crate::platforms::pumpfun::curve::{CurveState, Quote, Side};
+use crate::platforms::pumpfun::events::{CreateEnvelope, TradeEnvelope};
+use crate::platforms::pumpfun::validation::{ValidationReport, validate_trades};
+use solana_sdk::pubkey::Pubkey;
+
+// Version du schéma JSON : à incrémenter à chaque changement incompatible des champs
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct ScanReport<'a> {
+    schema_version: u32,
+    token: &'a TokenPreflight,
+    create: Option<&'a CreateEnvelope>,
+    bonding_curve: Option<&'a BondingCurve>,
+    stats: &'a TokenStats,
+    // Signatures de la fenêtre non récupérées : les chiffres sont alors partiels
+    missing_signatures: Vec<&'a str>,
+    trades: &'a [TradeEnvelope],
+    snipers: &'a SniperReport,
+    bundles: &'a BundleReport,
+    dev: Option<&'a DevReport>,
+    validation: &'a ValidationReport,
+}
+
+// Position d'un wallet avec son solde et son PnL selon les deux méthodes de coût
+#[derive(Serialize)]
+struct PositionReport<'a> {
+    #[serde(flatten)]
+    position: &'a WalletPosition,
+    tokens_held: u64,
+    fifo: Pnl,
+    average: Pnl,
+}
+
+impl<'a> From<&'a WalletPosition> for PositionReport<'a> {
+    fn from(position: &'a WalletPosition) -> Self {
+        Self {
+            position,
+            tokens_held: position.position(),
+            fifo: position.pnl(CostMethod::Fifo),
+            average: position.pnl(CostMethod::Average),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WalletReport<'a> {
+    #[serde(serialize_with = "base58::pubkey")]
+    wallet: Pubkey,
+    #[serde(serialize_with = "base58::pubkey")]
+    mint: Pubkey,
+    trades: Vec<&'a TradeEnvelope>,
+    position: Option<PositionReport<'a>>,
+}
+
+#[derive(Serialize)]
+struct LedgerReport<'a> {
+    method: CostMethod,
+    mark: Option<&'a CurveState>,
+    positions: Vec<PositionReport<'a>>,
+}
+
+#[derive(Serialize)]
+struct QuoteReport<'a> {
+    #[serde(serialize_with = "base58::pubkey")]
+    mint: Pubkey,
+    curve: &'a CurveState,
+    quote: &'a Quote,
+    graduation: Option<Quote>,
+}
+
+#[derive(Serialize)]
+struct CreatorReport<'a> {
+    #[serde(flatten)]
+    history: &'a CreatorHistory,
+    previous_launches: usize,
+    graduated: usize,
+    rugger_score: f64,
+}
+
+pub fn print_scan(format: OutputFormat, analysis: &TokenAnalysis) -> error::Result<()> {
+    let preflight = &analysis.preflight;
+    let stats = TokenStats::new(&analysis.trades);
+    let validation = validate_trades(analysis.create(), &analysis.trades);
+    match format {
+        OutputFormat::Json => {
+            let report = ScanReport {
+                schema_version: REPORT_SCHEMA_VERSION,
+                token: preflight,
+                create: analysis.creates.first(),
+                bonding_curve: analysis.bonding_curve.as_ref(),
+                stats: &stats,
+                missing_signatures: analysis.failures.iter().map(|f| f.signature.as_str()).collect(),
+                trades: &analysis.trades,
+                snipers: &analysis.snipers,
+                bundles: &analysis.bundles,
+                dev: analysis.dev.as_ref(),
+                validation: &validation,
+            };
+            println!("{}", serde_json::to_string(&report)?);
+        }
+        OutputFormat::Debug => {
+            println!("Mint: {:?}", preflight.mint);
+            println!("BondingCurve: {:?}", analysis.bonding_curve);
+            println!("Snipers: {:?}", analysis.snipers);
+            println!("Bundles: {:?}", analysis.bundles);
+            println!("Dev: {:?}", analysis.dev);
+            println!("CreateEvent: {:?}", analysis.creates);
+            println!("TokenStats: {:?}", stats);
+            println!("Validation: {:?}", validation);
+            if !analysis.is_complete() {
+                println!("Failures: {:?}", analysis.failures);
+            }
+        }
+        OutputFormat::Text => {
+            println!("Token       : {}", preflight.token_address);
+            println!("Program     : {}", preflight.mint.program);
+            match &preflight.platform {
+                Some(platform) => println!("Platform    : {platform}"),
+                None => println!("Platform    : unknown"),
+            }
+            println!(
+                "History     : {} signatures over {} page(s){}",
+                preflight.history.signatures_fetched,
+                preflight.history.pages,
+                if preflight.history.complete { "" } else { " (partial)" }
+            );
+            println!(
+                "Window      : {} ({} transactions)",
+                preflight.window,
+                preflight.transactions_to_analyze.len()
+            );
+            if !analysis.is_complete() {
+                println!(
+                    "Incomplete  : {} transaction(s) could not be fetched or decoded",
+                    analysis.failures.len()
+                );
+                for failure in &analysis.failures {
+                    println!(
+                        "  {} ({} after {} attempt(s)): {}",
+                        failure.signature, failure.kind, failure.attempts, failure.message
+                    );
+                }
+            }
+            if let Some(creation) = &preflight.creation {
+                println!(
+                    "Created     : slot {}{} ({})",
+                    creation.slot,
+                    creation.block_time.map(|t| format!(" at {t}")).unwrap_or_default(),
+                    creation.signature
+                );
+            }
+            if let Some(create) = analysis.create() {
+                println!("Name        : {} ({})", create.name, create.symbol);
+                println!("Creator     : {}", create.creator);
+            } else if let Some(curve) = &analysis.bonding_curve {
+                // Création hors fenêtre : la courbe garde la trace du créateur
+                println!("Creator     : {}", curve.creator);
+            }
+            if let Some(curve) = &analysis.bonding_curve {
+                print_curve(curve);
+            }
+            let flags = preflight.mint.risk_flags();
+            if !flags.is_empty() {
+                println!("Risk flags  : {}", flags.len());
+                for flag in flags {
+                    println!("  ⚠️ {flag}");
+                }
+            }
+            print_stats(&stats);
+            print_snipers(&analysis.snipers);
+            if let Some(dev) = &analysis.dev {
+                print_dev(dev);
+            }
+            print_bundles(&analysis.bundles);
+            print_validation(&validation);
+        }
+    }
+    Ok(())
+}
+
+// `summary` : le tableau déjà trié ; en JSON les rapports ont été écrits token par token
+pub fn print_batch(format: OutputFormat, batch: &BatchReport, summary: &DataFrame) {
+    match format {
+        OutputFormat::Json => {}
+        OutputFormat::Debug => println!("Batch: {:?}", batch.entries),
+        OutputFormat::Text => {
+            println!("Tokens      : {}", batch.entries.len());
+            println!("Failed      : {}", batch.failed().count());
+            for entry in batch.failed() {
+                if let Err(e) = &entry.outcome {
+                    println!("  {} {e}", entry.mint);
+                }
+            }
+            println!("{summary}");
+        }
+    }
+}
+
+// Une ligne par lancement, au fil de l'eau
+pub fn print_launch(format: OutputFormat, launch: &CreateEnvelope) -> error::Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string(launch)?),
+        OutputFormat::Debug => println!("Launch: {:?}", launch),
+        OutputFormat::Text => println!(
+            "slot {} {} {:<10} {} creator {} ({})",
+            launch.slot, launch.mint, launch.symbol, launch.name, launch.creator, launch.signature
+        ),
+    }
+    Ok(())
+}
+
+pub fn print_wallet(
+    format: OutputFormat,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    trades: &[TradeEnvelope],
+    position: Option<&WalletPosition>,
+) -> error::Result<()> {
+    let own: Vec<&TradeEnvelope> = trades.iter().filter(|t| t.user == *wallet).collect();
+    match format {
+        OutputFormat::Json => {
+            let report = WalletReport {
+                wallet: *wallet,
+                mint: *mint,
+                trades: own,
+                position: position.map(PositionReport::from),
+            };
+            println!("{}", serde_json::to_string(&report)?);
+        }
+        OutputFormat::Debug => {
+            println!("Trades: {:?}", own);
+            println!("Position: {:?}", position);
+        }
+        OutputFormat::Text => {
+            println!("Wallet      : {wallet}");
+            println!("Token       : {mint}");
+            println!("Trades      : {}", own.len());
+            for t in own {
+                println!(
+                    "  {} {:>4} {:>16} tokens {:>14.9} SOL  {}",
+                    t.timestamp,
+                    if t.is_buy { "BUY" } else { "SELL" },
+                    t.token_amount,
+                    t.sol_amount as f64 / LAMPORTS_PER_SOL,
+                    t.signature,
+                );
+            }
+            if let Some(position) = position {
+                let sol = |lamports: i128| lamports as f64 / LAMPORTS_PER_SOL;
+                println!("Position    : {} tokens", position.position() as f64 / TOKEN_UNIT);
+                println!(
+                    "SOL         : {:.4} spent / {:.4} received",
+                    sol(position.sol_spent as i128),
+                    sol(position.sol_received as i128)
+                );
+                for (label, method) in [("PnL (FIFO)  ", CostMethod::Fifo), ("PnL (avg)   ", CostMethod::Average)] {
+                    let pnl = position.pnl(method);
+                    println!(
+                        "{label}: {:+.4} SOL ({:+.4} realized / {:+.4} unrealized)",
+                        sol(pnl.total()),
+                        sol(pnl.realized),
+                        sol(pnl.unrealized)
+                    );
+                }
+                if position.tokens_sold_unmatched > 0 {
+                    println!(
+                        "Unmatched   : {} tokens sold without a buy in the window",
+                        position.tokens_sold_unmatched as f64 / TOKEN_UNIT
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_ledger(format: OutputFormat, ledger: &WalletLedger, method: CostMethod, top: usize) -> error::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let report = LedgerReport {
+                method,
+                mark: ledger.mark.as_ref(),
+                positions: ledger.positions.iter().take(top).map(PositionReport::from).collect(),
+            };
+            println!("{}", serde_json::to_string(&report)?);
+        }
+        OutputFormat::Debug => println!("Ledger: {:?}", ledger),
+        OutputFormat::Text => {
+            println!("Wallets     : {}", ledger.positions.len());
+            if let Some(mark) = &ledger.mark {
+                println!("Mark price  : {:.10} SOL", mark.spot_price());
+            }
+            let frame = ledger.frame(method)?;
+            println!("{}", frame.head(Some(top)));
+        }
+    }
+    Ok(())
+}
+
+pub fn print_candles(format: OutputFormat, resolution: Resolution, candles: &DataFrame) -> error::Result<()> {
+    match format {
+        // Un tableau JSON de bougies, une ligne par bougie comme avec --out
+        OutputFormat::Json => {
+            JsonWriter::new(std::io::stdout())
+                .with_json_format(JsonFormat::Json)
+                .finish(&mut candles.clone())?;
+            println!();
+        }
+        OutputFormat::Debug => println!("Candles: {:?}", candles),
+        OutputFormat::Text => {
+            println!("Resolution  : {resolution}");
+            println!("Candles     : {}", candles.height());
+            println!("{candles}");
+        }
+    }
+    Ok(())
+}
+
+pub fn print_quote(
+    format: OutputFormat,
+    mint: &Pubkey,
+    curve: &CurveState,
+    quote: &Quote,
+    graduation: Option<Quote>,
+) -> error::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let report = QuoteReport {
+                mint: *mint,
+                curve,
+                quote,
+                graduation,
+            };
+            println!("{}", serde_json::to_string(&report)?);
+        }
+        OutputFormat::Debug => {
+            println!("Curve: {:?}", curve);
+            println!("Quote: {:?}", quote);
+            println!("Graduation: {:?}", graduation);
+        }
+        OutputFormat::Text => {
+            let sol = |lamports: u64| lamports as f64 / LAMPORTS_PER_SOL;
+            println!("Token       : {mint}");
+            println!("Spot price  : {:.10} SOL", quote.price_before);
+            println!("Market cap  : {:.2} SOL", curve.market_cap_sol());
+            println!("Side        : {}", quote.side);
+            println!("Tokens      : {:.6}", quote.token_amount as f64 / TOKEN_UNIT);
+            println!("Curve SOL   : {:.9} SOL", sol(quote.sol_amount));
+            println!("Fees        : {:.9} SOL protocol / {:.9} SOL creator", sol(quote.fee), sol(quote.creator_fee));
+            match quote.side {
+                Side::Buy => println!("You pay     : {:.9} SOL", sol(quote.total_sol)),
+                Side::Sell => println!("You receive : {:.9} SOL", sol(quote.total_sol)),
+            }
+            println!("Avg price   : {:.10} SOL", quote.average_price);
+            println!("Price after : {:.10} SOL", quote.price_after);
+            println!("Impact      : {:+.2}%", quote.price_impact * 100.0);
+            println!(
+                "Progress    : {:.1}% -> {:.1}%",
+                curve.progress() * 100.0,
+                quote.after.progress() * 100.0
+            );
+            if let Some(graduation) = graduation {
+                println!("To graduate : {:.4} SOL (fees included)", sol(graduation.total_sol));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_holders(format: OutputFormat, snapshot: &HolderSnapshot, top: usize) -> error::Result<()> {
+    match format {
+        // Les mêmes holders que la sortie texte : comptes labellisés puis les `top` premiers
+        OutputFormat::Json => {
+            let unlabelled = snapshot.holders.iter().filter(|h| h.label.is_none()).take(top);
+            let listed = HolderSnapshot {
+                mint: snapshot.mint,
+                supply: snapshot.supply,
+                decimals: snapshot.decimals,
+                token_accounts: snapshot.token_accounts,
+                holders: snapshot.labelled().chain(unlabelled).cloned().collect::<Vec<Holder>>(),
+                concentration: snapshot.concentration,
+            };
+            println!("{}", serde_json::to_string(&listed)?);
+        }
+        OutputFormat::Debug => {
+            println!("HolderSnapshot: {:?}", snapshot);
+        }
+        OutputFormat::Text => {
+            let concentration = &snapshot.concentration;
+            let unit = 10f64.powi(snapshot.decimals as i32);
+            println!("Token       : {}", snapshot.mint);
+            println!("Supply      : {:.2}", snapshot.supply as f64 / unit);
+            println!(
+                "Holders     : {} ({} token accounts, labelled accounts excluded)",
+                concentration.holders, snapshot.token_accounts
+            );
+            println!("Top 10      : {:.2}% of supply", concentration.top10_share * 100.0);
+            println!("Gini        : {:.3}", concentration.gini);
+            println!("Herfindahl  : {:.4}", concentration.herfindahl);
+            for holder in snapshot.labelled() {
+                println!(
+                    "  {} {:>6.2}% {}",
+                    holder.owner,
+                    holder.supply_share * 100.0,
+                    holder.label.map(|l| l.to_string()).unwrap_or_default()
+                );
+            }
+            for (rank, holder) in snapshot.holders.iter().filter(|h| h.label.is_none()).take(top).enumerate() {
+                println!(
+                    "  #{:<3} {} {:>6.2}% {:>16.2} tokens{}",
+                    rank + 1,
+                    holder.owner,
+                    holder.supply_share * 100.0,
+                    holder.amount as f64 / unit,
+                    if holder.token_accounts > 1 {
+                        format!(" ({} accounts)", holder.token_accounts)
+                    } else {
+                        String::new()
+                    }
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_creator_history(format: OutputFormat, history: &CreatorHistory) -> error::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let report = CreatorReport {
+                history,
+                previous_launches: history.previous().count(),
+                graduated: history.graduated(),
+                rugger_score: history.rugger_score(),
+            };
+            println!("{}", serde_json::to_string(&report)?);
+        }
+        OutputFormat::Debug => {
+            println!("CreatorHistory: {:?}", history);
+        }
+        OutputFormat::Text => {
+            println!("Creator     : {}", history.creator);
+            println!(
+                "History     : {} signature(s){}",
+                history.signatures_scanned,
+                if history.history_complete { "" } else { " (truncated)" }
+            );
+            println!(
+                "Launches    : {} previous, {} graduated",
+                history.previous().count(),
+                history.graduated()
+            );
+            println!("Rugger score: {:.0}/100", history.rugger_score());
+            for launch in &history.launches {
+                println!(
+                    "  {} {:<10} slot {} t={} {} peak {} last dev sell {}{}{}",
+                    launch.mint,
+                    launch.symbol,
+                    launch.slot,
+                    launch.launched_at,
+                    match launch.graduated {
+                        Some(true) => "graduated",
+                        Some(false) => "bonding",
+                        None => "unknown",
+                    },
+                    launch
+                        .peak_market_cap_sol
+                        .map(|mc| format!("{mc:.2} SOL"))
+                        .unwrap_or_else(|| "n/a".to_string()),
+                    launch
+                        .time_to_last_sell()
+                        .map(|t| format!("+{t}s"))
+                        .unwrap_or_else(|| "none".to_string()),
+                    if launch.trades_complete { "" } else { " (partial)" },
+                    if Some(launch.mint) == history.current { " <- current" } else { "" },
+                );
+                println!("    {} ({}), {} trade(s) seen, {}", launch.name, launch.symbol, launch.trades_seen, launch.signature);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_curve(curve: &BondingCurve) {
+    println!("Price       : {:.10} SOL", curve.price_sol());
+    println!("Market cap  : {:.2} SOL", curve.market_cap_sol());
+    println!("SOL raised  : {:.4} SOL", curve.sol_raised());
+    println!(
+        "Progress    : {:.1}%{}",
+        curve.progress() * 100.0,
+        if curve.complete { " (graduated)" } else { "" }
+    );
+}
+
+fn print_snipers(report: &SniperReport) {
+    if report.snipers.is_empty() {
+        return;
+    }
+    let sol = |lamports: u64| lamports as f64 / LAMPORTS_PER_SOL;
+    let reference = match report.reference {
+        Some(LaunchReference::Slot(slot)) => format!("within {} slot(s) of slot {slot}", report.options.max_slot_delay),
+        Some(LaunchReference::Timestamp(ts)) => format!("within ~{} slot(s) of t={ts}", report.options.max_slot_delay),
+        None => String::new(),
+    };
+    println!(
+        "Snipers     : {} {reference}, {:.2}% of supply, {} sold into the first {} retail buys{}",
+        report.snipers.len(),
+        report.supply_share() * 100.0,
+        report.sold_into_first_wave(),
+        report.options.first_wave,
+        report.first_wave_end.map(|slot| format!(" (until slot {slot})")).unwrap_or_default(),
+    );
+    for sniper in &report.snipers {
+        println!(
+            "  {}{} {} {:>6.2}% supply {:>10.4} SOL in {:>10.4} SOL out, {:>5.1}% sold{}{}",
+            sniper.wallet,
+            if sniper.is_creator { " (creator)" } else { "" },
+            match sniper.slot_delay {
+                Some(0) => "same slot".to_string(),
+                Some(delay) => format!("+{delay} slot(s)"),
+                None => "clock".to_string(),
+            },
+            sniper.supply_share * 100.0,
+            sol(sniper.sol_spent),
+            sol(sniper.sol_received),
+            sniper.sold_ratio() * 100.0,
+            sniper.first_sell.map(|slot| format!(", first sell at slot {slot}")).unwrap_or_default(),
+            if sniper.sold_into_first_wave { ", dumped on first wave" } else { "" },
+        );
+    }
+}
+
+fn print_dev(dev: &DevReport) {
+    let sol = |lamports: u64| lamports as f64 / LAMPORTS_PER_SOL;
+    let timing = |trade: &DevTrade| match (trade.slots_after_launch, trade.seconds_after_launch) {
+        (Some(slots), Some(seconds)) => format!("+{slots} slot(s) / +{seconds}s"),
+        (Some(slots), None) => format!("+{slots} slot(s)"),
+        (None, Some(seconds)) => format!("+{seconds}s"),
+        (None, None) => format!("t={}", trade.timestamp),
+    };
+    println!(
+        "Dev         : {}{}{}",
+        dev.wallet,
+        dev.deployer.map(|d| format!(" (deployed by {d})")).unwrap_or_default(),
+        if dev.sold_out() { " ⚠️ sold out" } else { "" }
+    );
+    match &dev.initial_buy {
+        Some(buy) => println!(
+            "  initial buy {:>14.2} tokens for {:.4} SOL at slot {} ({}){}",
+            buy.token_amount as f64 / TOKEN_UNIT,
+            sol(buy.sol_amount),
+            buy.slot,
+            timing(buy),
+            if dev.initial_buy_in_create_tx { ", in the create transaction" } else { "" }
+        ),
+        None => println!("  no dev buy in the analysed window"),
+    }
+    println!(
+        "  bought {:.2} tokens, sold {:.2} in {} sell(s), creator fees {:.6} SOL",
+        dev.tokens_bought as f64 / TOKEN_UNIT,
+        dev.tokens_sold() as f64 / TOKEN_UNIT,
+        dev.sells.len(),
+        sol(dev.creator_fees)
+    );
+    for sell in &dev.sells {
+        println!(
+            "  sell {:>14.2} tokens for {:>10.4} SOL at slot {} ({}) {}",
+            sell.token_amount as f64 / TOKEN_UNIT,
+            sol(sell.sol_amount),
+            sell.slot,
+            timing(sell),
+            sell.signature
+        );
+    }
+    if !dev.transfers_checked {
+        println!("  transfers out: not checked");
+        return;
+    }
+    if !dev.transfers.is_empty() {
+        println!(
+            "  transfers out: {} ({:.2} tokens)",
+            dev.transfers.len(),
+            dev.tokens_transferred() as f64 / TOKEN_UNIT
+        );
+    }
+    for transfer in &dev.transfers {
+        println!(
+            "  transfer {:>14.2} tokens to {} at slot {} {}",
+            transfer.amount as f64 / TOKEN_UNIT,
+            transfer.to,
+            transfer.slot,
+            transfer.signature
+        );
+    }
+}
+
+fn print_bundles(report: &BundleReport) {
+    if report.bundles.is_empty() && report.funders.is_empty() {
+        return;
+    }
+    let sol = |lamports: u64| lamports as f64 / LAMPORTS_PER_SOL;
+    println!(
+        "Bundles     : {}, {:.2}% of supply bundled, {:.2}% still held",
+        report.bundles.len(),
+        report.bundled_supply_share * 100.0,
+        report.held_supply_share * 100.0,
+    );
+    for bundle in &report.bundles {
+        println!(
+            "  slot {}{} {} wallet(s) {} tx {:>10.4} SOL in {:>14.2} tokens{}{}",
+            bundle.slot,
+            bundle.positions.map(|(first, last)| format!(" [{first}..{last}]")).unwrap_or_default(),
+            bundle.wallets.len(),
+            bundle.signatures.len(),
+            sol(bundle.sol_spent),
+            bundle.tokens_bought as f64 / TOKEN_UNIT,
+            if bundle.tip_lamports > 0 { format!(", jito tip {:.6} SOL", sol(bundle.tip_lamports)) } else { String::new() },
+            bundle.common_funder.map(|f| format!(", funded by {f}")).unwrap_or_default(),
+        );
+    }
+    for (funder, wallets) in &report.funders {
+        println!("  funder {funder} -> {} buyer(s)", wallets.len());
+    }
+}
+
+fn print_validation(validation: &ValidationReport) {
+    if validation.trades_checked == 0 {
+        return;
+    }
+    if validation.is_consistent() {
+        println!("Consistency : ok ({} trades replayed)", validation.trades_checked);
+        return;
+    }
+    println!(
+        "Consistency : {} gap(s), {} mismatch(es) over {} trades",
+        validation.gaps.len(),
+        validation.mismatches.len(),
+        validation.trades_checked
+    );
+    for gap in &validation.gaps {
+        println!(
+            "  gap {} -> {}: {:+.9} SOL / {:+} token units unaccounted for",
+            gap.after_signature.as_deref().unwrap_or("create"),
+            gap.before_signature,
+            gap.missing_sol as f64 / LAMPORTS_PER_SOL,
+            gap.missing_tokens,
+        );
+    }
+    for mismatch in &validation.mismatches {
+        println!(
+            "  {}: {} (expected {}, got {})",
+            mismatch.signature, mismatch.kind, mismatch.expected, mismatch.actual
+        );
+    }
+}
+
+fn print_stats(stats: &TokenStats) {
+    println!("Trades      : {} ({} buys / {} sells)", stats.total_trades, stats.buy_count, stats.sell_count);
+    println!("Volume      : {:.4} SOL ({:.4} buy / {:.4} sell)", stats.total_volume_sol, stats.buy_volume_sol, stats.sell_volume_sol);
+    println!("Makers      : {} ({} buyers / {} sellers)", stats.makers_count, stats.buyers_count, stats.sellers_count);
+    if let Some(v) = stats.avg_trades_per_second {
+        println!("Trades/sec  : {v:.2}");
+    }
+    if let Some(v) = stats.avg_volume_per_wallet_sol {
+        println!("Vol/wallet  : {v:.4} SOL");
+    }
+    println!("Coverage    : {}/{} s ({:.1}%)", stats.seconds_with_trades, stats.full_range_len, stats.coverage_ratio * 100.0);
+}
@@ -0,0 +1,50 @@
+use crate::domain::analysis::TokenAnalysis;
+use crate::domain::batch::{BatchEntry, BatchReport, TokenSummary};
+use crate::domain::base58::parse_pubkey;
+use crate::infra::rpc_backend::RpcBackend;
+use crate::infra::tx_cache::TxCache;
+use crate::services::preflight::{AnalysisOptions, run_analysis};
+use futures::{StreamExt, stream};
+
+// Analyse plusieurs mints en parallèle. Toutes les analyses partagent `rpc`, donc son limiteur :
+// la concurrence ne fait qu'occuper le budget RPC, elle ne l'augmente pas.
+// `inspect` voit chaque analyse réussie avant qu'elle soit réduite à son résumé.
+pub async fn scan_batch<F>(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    mints: Vec<String>,
+    options: &AnalysisOptions,
+    concurrency: usize,
+    mut inspect: F,
+) -> BatchReport
+where
+    F: FnMut(&TokenAnalysis),
+{
+    let total = mints.len();
+    let futs = mints.into_iter().map(|mint| async move {
+        let analysis = match parse_pubkey(&mint) {
+            Ok(token_address) => run_analysis(rpc, cache, token_address, options).await,
+            Err(e) => Err(e),
+        };
+        (mint, analysis)
+    });
+    let mut results = stream::iter(futs).buffer_unordered(concurrency.max(1));
+
+    let mut report = BatchReport::default();
+    while let Some((mint, analysis)) = results.next().await {
+        let outcome = match analysis {
+            Ok(analysis) => {
+                inspect(&analysis);
+                Ok(TokenSummary::from(&analysis))
+            }
+            Err(e) => {
+                tracing::error!("❌ {mint}: {e}");
+                Err(e.to_string())
+            }
+        };
+        report.entries.push(BatchEntry { mint, outcome });
+        tracing::info!(done = report.entries.len(), total, "📋 lot en cours");
+    }
+    tracing::info!(total, failed = report.failed().count(), "✅ lot terminé");
+    report
+}
@@ -0,0 +1,154 @@
+use crate::domain::bundles::{BundleEvidence, BundleReport, JITO_TIP_ACCOUNTS, detect_bundles};
+use crate::domain::decoder::helpers::extract_signature;
+use crate::domain::history::HistoryLimit;
+use crate::infra::rpc_backend::RpcBackend;
+use crate::infra::solana_rpc::{extract_account_keys, fetch_signature_history, fetch_transaction};
+use crate::infra::tx_cache::TxCache;
+use crate::platforms::pumpfun::events::TradeEnvelope;
+use futures::{StreamExt, stream};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::str::FromStr;
+
+// Au-delà, le wallet a trop d'activité pour qu'on remonte jusqu'à son financement
+const FUNDER_HISTORY: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundleOptions {
+    // Nombre maximal de wallets dont on cherche la source de financement
+    pub funder_lookups: usize,
+}
+
+impl Default for BundleOptions {
+    fn default() -> Self {
+        Self {
+            funder_lookups: 50,
+        }
+    }
+}
+
+// `transactions` : celles déjà récupérées par `retrieve_transactions`, dont on relit les métadonnées ;
+// `trades` : ordonnés, avec leur position dans le bloc quand elle est connue
+pub async fn analyze_bundles(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    transactions: &[EncodedConfirmedTransactionWithStatusMeta],
+    trades: &[TradeEnvelope],
+    total_supply: u64,
+    tx_config: RpcTransactionConfig,
+    options: BundleOptions,
+) -> BundleReport {
+    let mut evidence = BundleEvidence {
+        tips: collect_tips(transactions),
+        ..Default::default()
+    };
+
+    // Seuls les slots avec plusieurs acheteurs peuvent contenir un bundle
+    let mut buyers_by_slot: HashMap<u64, HashSet<Pubkey>> = HashMap::new();
+    for trade in trades.iter().filter(|t| t.is_buy) {
+        buyers_by_slot.entry(trade.slot).or_default().insert(trade.user);
+    }
+    let candidate_slots: BTreeSet<u64> = buyers_by_slot
+        .iter()
+        .filter(|(_, buyers)| buyers.len() > 1)
+        .map(|(slot, _)| *slot)
+        .collect();
+
+    let mut wallets: Vec<Pubkey> = Vec::new();
+    for trade in trades.iter().filter(|t| t.is_buy && candidate_slots.contains(&t.slot)) {
+        if !wallets.contains(&trade.user) {
+            wallets.push(trade.user);
+        }
+    }
+    wallets.truncate(options.funder_lookups);
+    evidence.funders = find_funders(rpc, cache, &wallets, tx_config).await;
+
+    detect_bundles(trades, &evidence, total_supply)
+}
+
+// Lamports envoyés aux comptes de tip Jito, lus dans les soldes avant/après
+fn collect_tips(transactions: &[EncodedConfirmedTransactionWithStatusMeta]) -> HashMap<String, u64> {
+    let mut tips = HashMap::new();
+    for tx in transactions {
+        let (Some(keys), Some(meta), Some(signature)) =
+            (extract_account_keys(tx), tx.transaction.meta.as_ref(), extract_signature(tx))
+        else {
+            continue;
+        };
+        let tip: u64 = keys
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| JITO_TIP_ACCOUNTS.contains(&key.as_str()))
+            .filter_map(|(i, _)| {
+                let (pre, post) = (meta.pre_balances.get(i)?, meta.post_balances.get(i)?);
+                post.checked_sub(*pre)
+            })
+            .sum();
+        if tip > 0 {
+            tips.insert(signature, tip);
+        }
+    }
+    tips
+}
+
+// La source d'un wallet est celui qui lui a envoyé des SOL dans sa toute première transaction
+async fn find_funders(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    wallets: &[Pubkey],
+    tx_config: RpcTransactionConfig,
+) -> HashMap<Pubkey, Pubkey> {
+    let futs = wallets.iter().map(|wallet| async move {
+        let funder = find_funder(rpc, cache, wallet, tx_config).await;
+        (*wallet, funder)
+    });
+    let funders: HashMap<Pubkey, Pubkey> = stream::iter(futs)
+        .buffer_unordered(rpc.max_concurrent())
+        .filter_map(|(wallet, funder)| async move { funder.map(|f| (wallet, f)) })
+        .collect()
+        .await;
+    tracing::info!(wallets = wallets.len(), funders = funders.len(), "🔗 sources de financement recherchées");
+    funders
+}
+
+async fn find_funder(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    wallet: &Pubkey,
+    tx_config: RpcTransactionConfig,
+) -> Option<Pubkey> {
+    let limit = HistoryLimit {
+        max_signatures: Some(FUNDER_HISTORY),
+        ..Default::default()
+    };
+    let history = match fetch_signature_history(rpc, wallet, &limit).await {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::warn!(%wallet, "historique du wallet non récupéré: {e}");
+            return None;
+        }
+    };
+    if !history.complete {
+        return None;
+    }
+    let oldest = history.signatures.iter().rev().find(|s| s.err.is_none())?;
+    let signature = Signature::from_str(&oldest.signature).ok()?;
+    let tx = fetch_transaction(rpc, cache, &signature, tx_config).await.ok().flatten()?;
+
+    // Le compte dont le solde baisse le plus a envoyé les SOL
+    let keys = extract_account_keys(&tx)?;
+    let meta = tx.transaction.meta.as_ref()?;
+    let own = wallet.to_string();
+    keys.iter()
+        .enumerate()
+        .filter(|(_, key)| **key != own)
+        .filter_map(|(i, key)| {
+            let decrease = meta.pre_balances.get(i)?.checked_sub(*meta.post_balances.get(i)?)?;
+            (decrease > 0).then_some((decrease, key))
+        })
+        .max_by_key(|(decrease, _)| *decrease)
+        .and_then(|(_, key)| Pubkey::from_str(key).ok())
+}
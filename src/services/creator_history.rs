@@ -0,0 +1,224 @@
+use crate::domain::creator_history::{CreatorHistory, LaunchOutcome};
+use crate::domain::decoder::account::{self as account_decoder, AccountKind};
+use crate::domain::decoder::event::{DecodedEvent, decode_transaction, order_events};
+use crate::domain::history::HistoryLimit;
+use crate::error::Result;
+use crate::infra::retry::RetryPolicy;
+use crate::infra::rpc_backend::RpcBackend;
+use crate::infra::solana_rpc::{fetch_signature_history, retrieve_transactions};
+use crate::infra::tx_cache::TxCache;
+use crate::platforms::pumpfun::accounts::BondingCurve;
+use crate::platforms::pumpfun::events::{CreateEnvelope, TradeEnvelope};
+use crate::platforms::pumpfun::pumpfun::PumpFun;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+
+// getMultipleAccounts accepte au plus 100 adresses
+const ACCOUNTS_PER_REQUEST: usize = 100;
+// Signatures listées par mint pour remonter jusqu'à sa création (10 pages)
+const TOKEN_HISTORY_SIGNATURES: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct CreatorHistoryOptions {
+    pub tx_config: RpcTransactionConfig,
+    pub retry: RetryPolicy,
+    // Signatures du créateur parcourues
+    pub max_signatures: usize,
+    // Nombre de lancements dont on reconstitue l'issue, les plus récents d'abord
+    pub max_tokens: usize,
+    // Transactions lues par mint pour retrouver les trades, en partant de la création
+    pub token_signatures: usize,
+}
+
+// Retrouve les CreateEvent émis par `creator` puis l'issue de chaque token
+pub async fn creator_history(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    creator: Pubkey,
+    current: Option<Pubkey>,
+    options: &CreatorHistoryOptions,
+) -> Result<CreatorHistory> {
+    let limit = HistoryLimit {
+        max_signatures: Some(options.max_signatures),
+        ..Default::default()
+    };
+    let history = fetch_signature_history(rpc, &creator, &limit).await?;
+    let signatures: Vec<String> = history
+        .signatures
+        .iter()
+        .filter(|s| s.err.is_none())
+        .map(|s| s.signature.clone())
+        .collect();
+    let fetched = retrieve_transactions(rpc, cache, signatures, options.tx_config, &options.retry).await;
+
+    // Historique du plus récent au plus ancien : on garde le premier CreateEvent vu par mint
+    let mut launches: Vec<CreateEnvelope> = Vec::new();
+    for tx in &fetched.transactions {
+        let (creates, _) = decode_pumpfun(tx);
+        for create in creates.into_iter().filter(|c| c.creator == creator || c.user == creator) {
+            if launches.iter().all(|l| l.mint != create.mint) {
+                launches.push(create);
+            }
+        }
+    }
+    tracing::info!(%creator, launches = launches.len(), "🧬 lancements du créateur retrouvés");
+    launches.truncate(options.max_tokens);
+
+    let curves = fetch_curves(rpc, &launches).await;
+    let mut outcomes = Vec::with_capacity(launches.len());
+    for (launch, curve) in launches.iter().zip(&curves) {
+        let (trades, complete) = token_trades(rpc, cache, &launch.mint, options).await;
+        outcomes.push(LaunchOutcome::new(launch, curve.as_ref(), &trades, complete));
+    }
+
+    Ok(CreatorHistory {
+        creator,
+        current,
+        launches: outcomes,
+        signatures_scanned: history.signatures.len(),
+        history_complete: history.complete,
+    })
+}
+
+// Les courbes d'abord, en lots : elles disent à elles seules si le token a gradué
+async fn fetch_curves(rpc: &dyn RpcBackend, launches: &[CreateEnvelope]) -> Vec<Option<BondingCurve>> {
+    let decoder = PumpFun;
+    let mut curves = Vec::with_capacity(launches.len());
+    for chunk in launches.chunks(ACCOUNTS_PER_REQUEST) {
+        let addresses: Vec<Pubkey> = chunk.iter().map(|l| BondingCurve::address(&l.mint)).collect();
+        match rpc.get_multiple_accounts(&addresses).await {
+            Ok(accounts) => curves.extend(accounts.into_iter().map(|account| {
+                let account = account?;
+                if account_decoder::AccountDecoder::classify(&decoder, &account.owner) != Some(AccountKind::BondingCurve) {
+                    return None;
+                }
+                account_decoder::AccountDecoder::decode_bonding_curve_account(&decoder, &account.data).ok()
+            })),
+            Err(e) => {
+                tracing::warn!("courbes de bonding non récupérées: {e}");
+                curves.extend(chunk.iter().map(|_| None));
+            }
+        }
+    }
+    curves
+}
+
+// Premiers trades d'un mint, dans la limite de `token_signatures` : le pic de capitalisation
+// d'un rug se trouve près de la création, pas dans les derniers trades
+async fn token_trades(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    mint: &Pubkey,
+    options: &CreatorHistoryOptions,
+) -> (Vec<TradeEnvelope>, bool) {
+    // Seules les signatures sont listées en entier : les transactions restent bornées
+    let limit = HistoryLimit {
+        max_signatures: Some(options.token_signatures.max(TOKEN_HISTORY_SIGNATURES)),
+        ..Default::default()
+    };
+    let history = match fetch_signature_history(rpc, mint, &limit).await {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::warn!(%mint, "historique du mint non récupéré: {e}");
+            return (Vec::new(), false);
+        }
+    };
+    if !history.complete {
+        tracing::warn!(%mint, signatures = history.signatures.len(), "création du mint hors d'atteinte, trades partiels");
+    }
+    let signatures: Vec<String> = history
+        .signatures
+        .iter()
+        .rev()
+        .filter(|s| s.err.is_none())
+        .map(|s| s.signature.clone())
+        .collect();
+    let truncated = signatures.len() > options.token_signatures;
+    let signatures: Vec<String> = signatures.into_iter().take(options.token_signatures).collect();
+    let fetched = retrieve_transactions(rpc, cache, signatures, options.tx_config, &options.retry).await;
+
+    let mut trades = Vec::new();
+    for tx in &fetched.transactions {
+        let (_, decoded) = decode_pumpfun(tx);
+        trades.extend(decoded.into_iter().filter(|t| t.mint == *mint));
+    }
+    order_events(&mut trades);
+    (trades, history.complete && !truncated && fetched.is_complete())
+}
+
+// Un log illisible ne doit pas faire échouer tout l'historique
+fn decode_pumpfun(tx: &EncodedConfirmedTransactionWithStatusMeta) -> (Vec<CreateEnvelope>, Vec<TradeEnvelope>) {
+    let mut creates = Vec::new();
+    let mut trades = Vec::new();
+    match decode_transaction(&PumpFun, tx) {
+        Ok(events) => {
+            for event in events {
+                match event.split() {
+                    DecodedEvent::Create(create) => creates.push(create),
+                    DecodedEvent::Trade(trade) => trades.push(trade),
+                }
+            }
+        }
+        Err(e) => tracing::warn!(slot = tx.slot, "événement pump.fun illisible: {e}"),
+    }
+    (creates, trades)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::curve::CurveState;
+    use crate::platforms::pumpfun::sample::SampleLaunch;
+    use solana_sdk::commitment_config::CommitmentConfig;
+    use solana_transaction_status_client_types::UiTransactionEncoding;
+
+    fn options(token_signatures: usize) -> CreatorHistoryOptions {
+        CreatorHistoryOptions {
+            tx_config: RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+            retry: RetryPolicy {
+                max_retries: 0,
+                ..RetryPolicy::default()
+            },
+            max_signatures: 1000,
+            max_tokens: 10,
+            token_signatures,
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_the_trades_closest_to_the_creation() {
+        let launch = SampleLaunch::new();
+        let rpc = launch.rpc().with_signatures(launch.creator, [launch.transactions[0].status()]);
+
+        let history = creator_history(&rpc, None, launch.creator, None, &options(3)).await.unwrap();
+
+        assert_eq!(history.launches.len(), 1);
+        let outcome = &history.launches[0];
+        assert_eq!(outcome.mint, launch.mint);
+        assert_eq!(outcome.trades_seen, 3);
+        assert!(!outcome.trades_complete);
+        // Le dev buy est dans la fenêtre : elle part bien de la création
+        assert_eq!(outcome.creator_last_sell, None);
+        let third = &launch.trade_envelopes()[2];
+        assert_eq!(outcome.peak_market_cap_sol, Some(CurveState::from(&third.event).market_cap_sol()));
+    }
+
+    #[tokio::test]
+    async fn a_short_history_is_read_in_full() {
+        let launch = SampleLaunch::new();
+        let rpc = launch.rpc().with_signatures(launch.creator, [launch.transactions[0].status()]);
+
+        let history = creator_history(&rpc, None, launch.creator, None, &options(200)).await.unwrap();
+
+        let outcome = &history.launches[0];
+        assert_eq!(outcome.trades_seen, launch.trade_envelopes().len());
+        assert!(outcome.trades_complete);
+        assert_eq!(outcome.graduated, Some(false));
+        assert_eq!(outcome.peak_market_cap_sol, Some(launch.curve.market_cap_sol()));
+    }
+}
@@ -0,0 +1,249 @@
+use crate::domain::decoder::helpers::extract_signature;
+use crate::domain::dev::TokenTransfer;
+use crate::domain::history::HistoryLimit;
+use crate::infra::rpc_backend::RpcBackend;
+use crate::infra::solana_rpc::{extract_account_keys, fetch_signature_history, retrieve_transactions};
+use crate::infra::tx_cache::TxCache;
+use crate::platforms::pumpfun::accounts::BondingCurve;
+use crate::services::preflight::AnalysisOptions;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, UiTransactionTokenBalance,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DevOptions {
+    // Signatures du wallet du dev parcourues pour trouver les transferts (0 : désactivé)
+    pub history: usize,
+}
+
+impl Default for DevOptions {
+    fn default() -> Self {
+        Self { history: 200 }
+    }
+}
+
+// Parcourt l'historique du dev depuis le lancement ; None si le parcours est désactivé ou a échoué
+pub async fn find_dev_transfers(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    dev: &Pubkey,
+    mint: &Pubkey,
+    launch_slot: Option<u64>,
+    options: &AnalysisOptions,
+) -> Option<Vec<TokenTransfer>> {
+    if options.dev.history == 0 {
+        return None;
+    }
+    let limit = HistoryLimit {
+        max_signatures: Some(options.dev.history),
+        min_slot: launch_slot,
+        ..Default::default()
+    };
+    let history = match fetch_signature_history(rpc, dev, &limit).await {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::warn!(%dev, "historique du dev non récupéré: {e}");
+            return None;
+        }
+    };
+    let signatures = history
+        .signatures
+        .iter()
+        .rev()
+        .filter(|s| s.err.is_none())
+        .map(|s| s.signature.clone())
+        .collect();
+    let fetched = retrieve_transactions(rpc, cache, signatures, options.tx_config, &options.retry).await;
+    let bonding_curve = BondingCurve::address(mint);
+
+    let transfers: Vec<TokenTransfer> = fetched
+        .transactions
+        .iter()
+        .flat_map(|tx| token_transfers(tx, mint, dev, &bonding_curve))
+        .collect();
+    tracing::info!(%dev, transfers = transfers.len(), "📤 transferts du dev recherchés");
+    Some(transfers)
+}
+
+// Tokens du mint quittant `from` vers d'autres propriétaires que la courbe.
+// Quand le dev encaisse des SOL, la transaction est une vente (courbe ou AMM) : les tokens
+// reçus par un PDA (réserve d'un pool) en sont la contrepartie, seuls les wallets restent des transferts.
+fn token_transfers(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    mint: &Pubkey,
+    from: &Pubkey,
+    bonding_curve: &Pubkey,
+) -> Vec<TokenTransfer> {
+    let (Some(meta), Some(keys)) = (tx.transaction.meta.as_ref(), extract_account_keys(tx)) else {
+        return Vec::new();
+    };
+    let signature = extract_signature(tx).unwrap_or_default();
+
+    let from_str = from.to_string();
+    let is_sale = keys
+        .iter()
+        .position(|k| *k == from_str)
+        .is_some_and(|i| meta.post_balances.get(i) > meta.pre_balances.get(i));
+
+    let mint = mint.to_string();
+    let mut deltas: HashMap<String, i128> = HashMap::new();
+    let pre: Option<&Vec<UiTransactionTokenBalance>> = meta.pre_token_balances.as_ref().into();
+    let post: Option<&Vec<UiTransactionTokenBalance>> = meta.post_token_balances.as_ref().into();
+    for (balances, sign) in [(pre, -1i128), (post, 1i128)] {
+        for balance in balances.into_iter().flatten().filter(|b| b.mint == mint) {
+            let owner: Option<&String> = balance.owner.as_ref().into();
+            let (Some(owner), Ok(amount)) = (owner, balance.ui_token_amount.amount.parse::<i128>()) else {
+                continue;
+            };
+            *deltas.entry(owner.clone()).or_default() += sign * amount;
+        }
+    }
+
+    if deltas.get(&from_str).is_none_or(|delta| *delta >= 0) {
+        return Vec::new();
+    }
+    let curve = bonding_curve.to_string();
+    deltas
+        .into_iter()
+        .filter(|(owner, delta)| *delta > 0 && *owner != from_str && *owner != curve)
+        .filter_map(|(owner, delta)| Some((Pubkey::from_str(&owner).ok()?, delta)))
+        .filter(|(owner, _)| !is_sale || owner.is_on_curve())
+        .map(|(to, delta)| TokenTransfer {
+            signature: signature.clone(),
+            slot: tx.slot,
+            to,
+            amount: delta as u64,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::pumpfun::sample::SampleLaunch;
+    use serde_json::{Value, json};
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+
+    const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbYsMD1ZKsmx9BDGtJ5z1KZbQ8";
+
+    struct Fixture {
+        launch: SampleLaunch,
+        curve: Pubkey,
+        // Wallet ordinaire : clé sur la courbe ed25519
+        wallet: Pubkey,
+        // Autorité d'un pool AMM : PDA, hors courbe
+        pool: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let launch = SampleLaunch::new();
+            let curve = BondingCurve::address(&launch.mint);
+            let wallet = Keypair::new_from_array([7; 32]).pubkey();
+            let pool = Pubkey::find_program_address(&[b"pool-authority"], &spl_token::ID).0;
+            Self { launch, curve, wallet, pool }
+        }
+
+        fn dev(&self) -> Pubkey {
+            self.launch.creator
+        }
+
+        // Transaction signée par le dev : soldes SOL du dev avant/après, soldes du mint par propriétaire
+        fn tx(&self, sol: (u64, u64), balances: &[(Pubkey, u64, u64)]) -> EncodedConfirmedTransactionWithStatusMeta {
+            let mut tx = serde_json::to_value(self.launch.transactions[0].encoded()).unwrap();
+            let token_balances = |post: bool| -> Value {
+                balances
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (owner, pre_amount, post_amount))| {
+                        let amount = if post { post_amount } else { pre_amount };
+                        json!({
+                            "accountIndex": index + 3,
+                            "mint": self.launch.mint.to_string(),
+                            "owner": owner.to_string(),
+                            "programId": TOKEN_PROGRAM,
+                            "uiTokenAmount": {
+                                "uiAmount": null,
+                                "decimals": 6,
+                                "amount": amount.to_string(),
+                                "uiAmountString": amount.to_string(),
+                            },
+                        })
+                    })
+                    .collect()
+            };
+            tx["meta"]["preBalances"] = json!([sol.0, 0, 0]);
+            tx["meta"]["postBalances"] = json!([sol.1, 0, 0]);
+            tx["meta"]["preTokenBalances"] = token_balances(false);
+            tx["meta"]["postTokenBalances"] = token_balances(true);
+            serde_json::from_value(tx).unwrap()
+        }
+
+        fn transfers(&self, tx: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<(Pubkey, u64)> {
+            let mut transfers: Vec<(Pubkey, u64)> = token_transfers(tx, &self.launch.mint, &self.dev(), &self.curve)
+                .into_iter()
+                .map(|t| (t.to, t.amount))
+                .collect();
+            transfers.sort();
+            transfers
+        }
+    }
+
+    #[test]
+    fn tokens_sent_to_a_wallet_are_a_transfer() {
+        let f = Fixture::new();
+        let tx = f.tx((1_000_000, 995_000), &[(f.dev(), 1_000, 0), (f.wallet, 0, 1_000)]);
+
+        let transfers = token_transfers(&tx, &f.launch.mint, &f.dev(), &f.curve);
+
+        assert_eq!(transfers.len(), 1);
+        assert_eq!((transfers[0].to, transfers[0].amount), (f.wallet, 1_000));
+        assert_eq!(transfers[0].signature, f.launch.transactions[0].signature.to_string());
+        assert_eq!(transfers[0].slot, f.launch.transactions[0].slot);
+    }
+
+    #[test]
+    fn sales_to_the_curve_or_a_pool_are_not_transfers() {
+        let f = Fixture::new();
+
+        let curve_sale = f.tx((1_000_000, 3_000_000), &[(f.dev(), 1_000, 0), (f.curve, 5_000, 6_000)]);
+        assert!(f.transfers(&curve_sale).is_empty());
+        let pool_sale = f.tx((1_000_000, 3_000_000), &[(f.dev(), 1_000, 0), (f.pool, 5_000, 6_000)]);
+        assert!(f.transfers(&pool_sale).is_empty());
+    }
+
+    #[test]
+    fn a_transfer_bundled_with_a_sale_is_still_reported() {
+        let f = Fixture::new();
+        // Le dev encaisse des SOL (vente au pool) et envoie le reste à un wallet dans la même transaction
+        let tx = f.tx(
+            (1_000_000, 3_000_000),
+            &[(f.dev(), 1_500, 0), (f.pool, 5_000, 6_000), (f.wallet, 0, 500)],
+        );
+
+        assert_eq!(f.transfers(&tx), vec![(f.wallet, 500)]);
+    }
+
+    #[test]
+    fn without_a_sale_tokens_sent_to_a_program_are_a_transfer() {
+        let f = Fixture::new();
+        // Dépôt dans un programme de verrouillage : le dev paie les frais, ne touche rien
+        let tx = f.tx((1_000_000, 995_000), &[(f.dev(), 1_000, 0), (f.pool, 0, 1_000)]);
+
+        assert_eq!(f.transfers(&tx), vec![(f.pool, 1_000)]);
+    }
+
+    #[test]
+    fn the_dev_receiving_tokens_is_not_a_transfer() {
+        let f = Fixture::new();
+        let buy = f.tx((3_000_000, 1_000_000), &[(f.dev(), 0, 1_000), (f.curve, 6_000, 5_000)]);
+        let incoming = f.tx((1_000_000, 995_000), &[(f.dev(), 0, 1_000), (f.wallet, 1_000, 0)]);
+
+        assert!(f.transfers(&buy).is_empty());
+        assert!(f.transfers(&incoming).is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::domain::decoder::mint::{TokenProgram, decode_token_account};
+use crate::domain::holders::{HolderLabel, HolderSnapshot};
+use crate::error::Result;
+use crate::infra::rpc_backend::RpcBackend;
+use crate::infra::solana_rpc::fetch_account;
+use crate::platforms::constants::{
+    INCINERATOR, METEORA_DLMM_PROGRAM_ID, PUMPFUN_PROGRAM_ID, PUMPSWAP_PROGRAM_ID, RAYDIUM_AMM_AUTHORITY,
+    RAYDIUM_AMM_PROGRAM_ID, RAYDIUM_CPMM_PROGRAM_ID,
+};
+use crate::platforms::pumpfun::accounts::BondingCurve;
+use crate::services::preflight::{current_epoch, ensure_token_is_mint};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+// Le mint occupe les 32 premiers octets d'un compte de token
+const MINT_OFFSET: usize = 0;
+// Owners les plus gros dont on vérifie le programme propriétaire
+const OWNERS_TO_CLASSIFY: usize = 100;
+
+pub async fn holder_snapshot(rpc: &dyn RpcBackend, mint: Pubkey) -> Result<HolderSnapshot> {
+    let mint_info = ensure_token_is_mint(&fetch_account(rpc, mint).await?, current_epoch(rpc).await)?;
+
+    // Les comptes Token-2022 ont une taille variable selon leurs extensions
+    let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(MINT_OFFSET, mint.as_ref()))];
+    let program = match mint_info.program {
+        TokenProgram::SplToken => {
+            filters.push(RpcFilterType::DataSize(spl_token::state::Account::LEN as u64));
+            spl_token::ID
+        }
+        TokenProgram::Token2022 => spl_token_2022::ID,
+    };
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = rpc.get_program_accounts(&program, config).await?;
+
+    let mut decoded = Vec::with_capacity(accounts.len());
+    for (address, account) in &accounts {
+        match decode_token_account(account) {
+            Some(Ok(info)) => decoded.push(info),
+            Some(Err(e)) => tracing::warn!(%address, "compte de token illisible: {e}"),
+            None => {}
+        }
+    }
+    tracing::info!(%mint, accounts = decoded.len(), "👥 comptes de token récupérés");
+
+    let mut labels = static_labels(&mint);
+    let mut by_amount: Vec<_> = decoded.iter().collect();
+    by_amount.sort_by_key(|a| std::cmp::Reverse(a.amount));
+    let mut seen = HashSet::new();
+    let candidates: Vec<Pubkey> = by_amount
+        .into_iter()
+        .map(|a| a.owner)
+        .filter(|owner| !labels.contains_key(owner) && seen.insert(*owner))
+        .take(OWNERS_TO_CLASSIFY)
+        .collect();
+    labels.extend(program_labels(rpc, &candidates).await);
+
+    Ok(HolderSnapshot::build(mint, mint_info.supply, mint_info.decimals, &decoded, &labels))
+}
+
+fn static_labels(mint: &Pubkey) -> HashMap<Pubkey, HolderLabel> {
+    let mut labels = HashMap::from([(BondingCurve::address(mint), HolderLabel::BondingCurve)]);
+    for (address, label) in [(RAYDIUM_AMM_AUTHORITY, HolderLabel::RaydiumPool), (INCINERATOR, HolderLabel::Burn)] {
+        labels.insert(Pubkey::from_str(address).expect("Wrong address"), label);
+    }
+    labels
+}
+
+// Un owner détenu par un programme de pool ou par pump.fun n'est pas un holder
+async fn program_labels(rpc: &dyn RpcBackend, owners: &[Pubkey]) -> HashMap<Pubkey, HolderLabel> {
+    let programs: HashMap<Pubkey, HolderLabel> = [
+        (PUMPFUN_PROGRAM_ID, HolderLabel::BondingCurve),
+        (PUMPSWAP_PROGRAM_ID, HolderLabel::PumpSwapPool),
+        (RAYDIUM_AMM_PROGRAM_ID, HolderLabel::RaydiumPool),
+        (RAYDIUM_CPMM_PROGRAM_ID, HolderLabel::RaydiumPool),
+        (METEORA_DLMM_PROGRAM_ID, HolderLabel::MeteoraPool),
+    ]
+    .into_iter()
+    .map(|(program, label)| (Pubkey::from_str(program).expect("Wrong address"), label))
+    .collect();
+
+    let mut labels = HashMap::new();
+    if owners.is_empty() {
+        return labels;
+    }
+    match rpc.get_multiple_accounts(owners).await {
+        Ok(accounts) => {
+            for (owner, account) in owners.iter().zip(accounts) {
+                if let Some(label) = account.and_then(|a| programs.get(&a.owner).copied()) {
+                    labels.insert(*owner, label);
+                }
+            }
+        }
+        Err(e) => tracing::warn!("owners non classés, les pools peuvent fausser les chiffres: {e}"),
+    }
+    labels
+}
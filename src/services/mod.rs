@@ -1 +1,7 @@
+pub mod batch;
+pub mod bundles;
+pub mod creator_history;
+pub mod dev;
+pub mod holders;
 pub mod preflight;
+pub mod watch;
@@ -1,77 +1,96 @@
+use crate::domain::history::HistoryLimit;
 use crate::infra::solana_rpc::extract_account_keys;
+use crate::infra::solana_rpc::fetch_signature_history;
+use crate::infra::solana_rpc::fetch_transaction;
+use crate::infra::tx_cache::TxCache;
 use crate::infra::solana_rpc::fetch_account;
-use crate::platforms::pumpfun::events::CreateEvent;
+use crate::platforms::pumpfun::events::{CreateEnvelope, TradeEnvelope};
+use crate::platforms::pumpfun::accounts::BondingCurve;
+use crate::domain::decoder::account::{self as account_decoder, AccountKind};
+use crate::domain::analysis::{AnalysisWindow, Creation, TokenAnalysis};
+use crate::infra::retry::RetryPolicy;
+use crate::domain::snipers::{SniperOptions, detect_snipers};
+use crate::services::bundles::{BundleOptions, analyze_bundles};
+use crate::services::dev::{DevOptions, find_dev_transfers};
+use crate::domain::dev::dev_report;
 use crate::{domain::analysis::TokenPreflight, error, error::Result};
 use solana_client::client_error::ClientError;
-use solana_client::nonblocking::rpc_client::RpcClient;
+use crate::infra::rpc_backend::RpcBackend;
 use solana_client::rpc_config::RpcTransactionConfig;
-use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::epoch_schedule::EpochSchedule;
 use solana_sdk::signature::Signature;
 use solana_sdk::{account::Account, pubkey::Pubkey};
-use solana_transaction_status_client_types::UiTransactionEncoding;
-use spl_token::ID;
-use crate::domain::decoder::helpers::extract_logs;
-use crate::platforms::pumpfun::events::TradeEvent;
+use crate::domain::decoder::mint::{MintInfo, decode_mint};
 use crate::platforms::platforms::Platform;
 use std::str::FromStr;
 use crate::platforms::pumpfun::pumpfun::PumpFun;
-use solana_transaction_status_client_types::EncodedTransaction;
-use crate::domain::decoder::event::EventDecoder;
-use crate::domain::decoder::event::EventKind;
+use crate::domain::decoder::event::{DecodedEvent, decode_transaction, order_events};
+use crate::domain::decoder::helpers::extract_signature;
+use crate::domain::fetch::{FailureKind, FetchFailure};
 use crate::platforms::utils::identify_platform;
-use crate::infra::solana_rpc::retrieve_transactions;
+use crate::infra::solana_rpc::{fetch_block_positions, retrieve_transactions};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-fn ensure_token_is_token_account(account: &Account) -> error::Result<()> {
-    match account.owner {
-        ID => Ok(()),
-        _ => Err(error::Error::NotAToken(account.owner)),
+// Accepte les mints SPL Token et Token-2022, extensions comprises
+pub fn ensure_token_is_mint(account: &Account, epoch: u64) -> error::Result<MintInfo> {
+    match decode_mint(account, epoch) {
+        Some(mint) => Ok(mint?),
+        None => Err(error::Error::NotAToken(account.owner)),
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct AnalysisOptions {
+    pub tx_config: RpcTransactionConfig,
+    pub window: AnalysisWindow,
+    pub history: HistoryLimit,
+    pub retry: RetryPolicy,
+    // Blocs lus pour ordonner les transactions d'un même slot (0 : ordre de récupération)
+    pub block_lookups: usize,
+    pub snipers: SniperOptions,
+    pub bundles: BundleOptions,
+    pub dev: DevOptions,
+}
+
 async fn preflight_token_check(
-    rpc_client: &RpcClient,
+    rpc_client: &dyn RpcBackend,
+    cache: Option<&TxCache>,
     token_address: Pubkey,
+    mint: MintInfo,
+    options: &AnalysisOptions,
 ) -> error::Result<TokenPreflight> {
     
-    let signatures = rpc_client
-        .get_signatures_for_address(&token_address)
-        .await?;
+    let mut history_limit = options.history.clone();
+    options.window.narrow(&mut history_limit);
+    let history = fetch_signature_history(rpc_client, &token_address, &history_limit).await?;
 
-    if signatures.is_empty() {
+    if history.signatures.is_empty() {
         return Err(error::Error::NoTransactionRecorded);
     }
-    if signatures.len() >= 1000 {
-        return Err(error::Error::TooManyTransactions {
-            transactions_fetched: signatures.len(),
-        });
-    }
 
-    let tx_sigs: Vec<String> = signatures.into_iter().filter(|signature| signature.err.is_none()).map(|s| s.signature).collect();
-    let last_element =  tx_sigs.len().saturating_sub(100);
-    let mut last_100: Vec<String> = tx_sigs[last_element..].to_vec();
-    last_100.reverse();
-    
+    let transactions_to_analyze = options.window.select(&history.signatures);
 
-    let creation_signature = tx_sigs.last().cloned().unwrap_or_default();
+    // La plus ancienne signature n'est la création que si l'historique est complet
+    let oldest_signature = history
+        .signatures
+        .iter()
+        .rev()
+        .find(|s| s.err.is_none())
+        .map(|s| s.signature.clone())
+        .ok_or(error::Error::NoTransactionRecorded)?;
 
-    let creation_sig = Signature::from_str(&creation_signature).map_err(|e| {
+    let oldest_sig = Signature::from_str(&oldest_signature).map_err(|e| {
         error::Error::Upstream(ClientError::from(std::io::Error::new(
             std::io::ErrorKind::Other,
             e,
         )))
     })?;
 
-    let config = RpcTransactionConfig {
-        encoding: Some(UiTransactionEncoding::Json),
-        commitment: Some(CommitmentConfig::confirmed()),
-        max_supported_transaction_version: Some(0), // laisse None pour accepter v0+
-    };
+    let oldest_tx = fetch_transaction(rpc_client, cache, &oldest_sig, options.tx_config)
+        .await?
+        .ok_or_else(|| error::Error::TransactionUnavailable(oldest_signature.clone()))?;
 
-    let creation_tx = rpc_client
-        .get_transaction_with_config(&creation_sig, config)
-        .await?;
-
-    let accounts = match extract_account_keys(&creation_tx) {
+    let accounts = match extract_account_keys(&oldest_tx) {
         Some(keys) => keys,
         None => {
             return Err(error::Error::Upstream(ClientError::from(
@@ -84,72 +103,111 @@ async fn preflight_token_check(
     };
 
     let platform = identify_platform(&accounts);
+    let creation = history.complete.then(|| Creation {
+        signature: oldest_signature.clone(),
+        slot: oldest_tx.slot,
+        block_time: oldest_tx.block_time,
+    });
 
     Ok(TokenPreflight::new(
         token_address,
+        mint,
         platform,
-        last_100,
+        options.window.clone(),
+        transactions_to_analyze,
+        creation,
+        history.summary(),
     ))
 }
 
-async fn token_preflight(rpc_client: &RpcClient, token_address: Pubkey) -> Result<TokenPreflight> {
+async fn token_preflight(rpc_client: &dyn RpcBackend, cache: Option<&TxCache>, token_address: Pubkey, options: &AnalysisOptions) -> Result<TokenPreflight> {
     let account = fetch_account(rpc_client, token_address).await?;
-    ensure_token_is_token_account(&account)?;
-    preflight_token_check(rpc_client, token_address).await
+    let mint = ensure_token_is_mint(&account, current_epoch(rpc_client).await)?;
+    preflight_token_check(rpc_client, cache, token_address, mint, options).await
 }
 
-pub async fn run_analysis(rpc_client: &RpcClient, token_address: Pubkey, config: &RpcTransactionConfig,) -> error::Result<(TokenPreflight, Vec<TradeEvent>, Vec<CreateEvent>)> {
+// Époque courante pour les frais de transfert Token-2022 ; sans slot, on retient les frais les plus récents
+pub async fn current_epoch(rpc_client: &dyn RpcBackend) -> u64 {
+    match rpc_client.get_slot().await {
+        Ok(slot) => EpochSchedule::without_warmup().get_epoch(slot),
+        Err(e) => {
+            tracing::warn!("slot courant non récupéré, frais de transfert les plus récents retenus: {e}");
+            u64::MAX
+        }
+    }
+}
+
+// La courbe n'est qu'un complément : un échec ici ne bloque pas l'analyse
+pub async fn fetch_bonding_curve(rpc_client: &dyn RpcBackend, mint: &Pubkey) -> Option<BondingCurve> {
+    let address = BondingCurve::address(mint);
+    let account = match rpc_client.get_multiple_accounts(&[address]).await {
+        Ok(mut accounts) => accounts.pop().flatten()?,
+        Err(e) => {
+            tracing::warn!(%address, "courbe de bonding non récupérée: {e}");
+            return None;
+        }
+    };
+
+    let decoder = PumpFun;
+    if account_decoder::AccountDecoder::classify(&decoder, &account.owner) != Some(AccountKind::BondingCurve) {
+        tracing::warn!(%address, owner = %account.owner, "compte de courbe inattendu");
+        return None;
+    }
+    match account_decoder::AccountDecoder::decode_bonding_curve_account(&decoder, &account.data) {
+        Ok(curve) => Some(curve),
+        Err(e) => {
+            tracing::warn!(%address, "courbe de bonding illisible: {e}");
+            None
+        }
+    }
+}
+
+pub async fn run_analysis(rpc_client: &dyn RpcBackend, cache: Option<&TxCache>, token_address: Pubkey, options: &AnalysisOptions) -> error::Result<TokenAnalysis> {
     
-    let preflight = token_preflight(&rpc_client, token_address).await?;
+    let preflight = token_preflight(rpc_client, cache, token_address, options).await?;
     tracing::info!(%preflight, "✅ token prêt pour analyse");
     
-    let txs = retrieve_transactions(
-        &rpc_client,
+    let mut fetched = retrieve_transactions(
+        rpc_client,
+        cache,
         preflight.transactions_to_analyze.clone(),
-        *config,
-    ).await?;
+        options.tx_config,
+        &options.retry,
+    ).await;
 
-    let mut decoded_create: Vec<CreateEvent> = Vec::new();
-    let mut decoded_trade: Vec<TradeEvent> = Vec::new();
+    let mut decoded_create: Vec<CreateEnvelope> = Vec::new();
+    let mut decoded_trade: Vec<TradeEnvelope> = Vec::new();
+    let mut bonding_curve: Option<BondingCurve> = None;
 
     match preflight.platform {
         Some(Platform::PumpFun) => {
             let my_platform = PumpFun;
 
-            for tx in &txs {
-                // a) Récupérer la signature lisible (utile à logguer/attacher au TradeEvent)
-                let signature = match &tx.transaction.transaction {
-                    EncodedTransaction::Json(inner) => inner
-                        .signatures
-                        .first()
-                        .map(|s| s.to_string())
-                        .unwrap_or_default(),
-                    _ => String::new(),
-                };
+            // État courant de la courbe, indépendant de la fenêtre analysée
+            bonding_curve = fetch_bonding_curve(rpc_client, &token_address).await;
 
-                // b) Extraire les blobs logs “Program data:” (Vec<Vec<u8>>)
-                if let Some(blobs) = extract_logs(tx) {
-                    for blob in blobs {
-                        // Selon ton impl: si `classify` attend le discriminant, sépare 8+payload ici.
-                        // Sinon, si elle gère le blob complet (disc en tête), passe `&blob` direct.
-                        if let Some(kind) = my_platform.classify(&blob) {
-                            match kind {
-                                EventKind::Create => {
-                                    // Utile si tu veux aucher le mint/creator au TGE
-                                    let create = my_platform.decode_create(&blob)?;
-                                    if create.mint == token_address {
-                                        decoded_create.push(create);
-                                    }
-                                }
-                                EventKind::Trade => {
-                                    // Ici ta signature est disponible si ton decode_trade en a besoin
-                                    let trade = my_platform.decode_trade(&signature, &blob)?;
-                                    if trade.mint == token_address {
-                                        decoded_trade.push(trade);
-                                    }
-                                }
-                            }
-                        }
+            // Chaque événement garde sa signature, son slot, son heure de bloc et son rang dans les logs.
+            // Un log illisible ne fait perdre que sa transaction, signalée comme échec : l'analyse est incomplète
+            for tx in &fetched.transactions {
+                let events = match decode_transaction(&my_platform, tx) {
+                    Ok(events) => events,
+                    Err(e) => {
+                        let signature = extract_signature(tx).unwrap_or_default();
+                        tracing::warn!(%signature, slot = tx.slot, "événement pump.fun illisible: {e}");
+                        fetched.failures.push(FetchFailure {
+                            signature,
+                            kind: FailureKind::Undecodable,
+                            attempts: 1,
+                            message: e.to_string(),
+                        });
+                        continue;
+                    }
+                };
+                for event in events {
+                    match event.split() {
+                        DecodedEvent::Create(create) if create.mint == token_address => decoded_create.push(create),
+                        DecodedEvent::Trade(trade) if trade.mint == token_address => decoded_trade.push(trade),
+                        _ => {}
                     }
                 }
             }
@@ -158,7 +216,258 @@ pub async fn run_analysis(rpc_client: &RpcClient, token_address: Pubkey, config:
         }
     }
 
+    attach_block_positions(rpc_client, &mut decoded_create, &mut decoded_trade, options.block_lookups).await;
+    order_events(&mut decoded_create);
+    order_events(&mut decoded_trade);
+    let create_tx = decoded_create.first().map(|create| Creation {
+        signature: create.signature.clone(),
+        slot: create.slot,
+        block_time: create.block_time,
+    });
+
     tracing::info!("✅ token prêt pour analyse: {} trades décodés", decoded_trade.len());
-    Ok((preflight, decoded_trade, decoded_create))
+    let creation = preflight.creation.clone().or(create_tx);
+    let creation_slot = creation.as_ref().map(|c| c.slot);
+    let create = decoded_create.first().map(|c| &c.event);
+    let snipers = detect_snipers(
+        &decoded_trade,
+        create,
+        creation_slot,
+        preflight.mint.supply,
+        options.snipers,
+    );
+    let bundles = analyze_bundles(
+        rpc_client,
+        cache,
+        &fetched.transactions,
+        &decoded_trade,
+        preflight.mint.supply,
+        options.tx_config,
+        options.bundles,
+    )
+    .await;
+
+    // Le créateur vient du CreateEvent, ou de la courbe si la création est hors fenêtre
+    let creator = create.map(|c| c.creator).or(bonding_curve.as_ref().map(|c| c.creator));
+    let dev = match creator {
+        Some(creator) => {
+            let transfers = find_dev_transfers(rpc_client, cache, &creator, &token_address, creation_slot, options).await;
+            Some(dev_report(creator, create, creation.as_ref(), &decoded_trade, transfers))
+        }
+        None => None,
+    };
+    Ok(TokenAnalysis {
+        preflight,
+        trades: decoded_trade,
+        creates: decoded_create,
+        bonding_curve,
+        snipers,
+        bundles,
+        dev,
+        failures: fetched.failures,
+    })
 
 }
+
+// Les transactions d'un même slot ne se départagent qu'avec la lecture du bloc, en commençant par les plus anciens slots
+async fn attach_block_positions(
+    rpc: &dyn RpcBackend,
+    creates: &mut [CreateEnvelope],
+    trades: &mut [TradeEnvelope],
+    max_blocks: usize,
+) {
+    let mut by_slot: BTreeMap<u64, HashSet<&str>> = BTreeMap::new();
+    for (slot, signature) in creates
+        .iter()
+        .map(|c| (c.slot, c.signature.as_str()))
+        .chain(trades.iter().map(|t| (t.slot, t.signature.as_str())))
+    {
+        by_slot.entry(slot).or_default().insert(signature);
+    }
+
+    let mut positions: HashMap<String, u32> = HashMap::new();
+    for (slot, wanted) in by_slot.iter().filter(|(_, signatures)| signatures.len() > 1).take(max_blocks) {
+        positions.extend(fetch_block_positions(rpc, *slot, wanted).await);
+    }
+    if positions.is_empty() {
+        return;
+    }
+    for create in creates.iter_mut() {
+        create.tx_index = positions.get(&create.signature).copied();
+    }
+    for trade in trades.iter_mut() {
+        trade.tx_index = positions.get(&trade.signature).copied();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::dev::DevReport;
+    use crate::platforms::pumpfun::sample::SampleLaunch;
+    use crate::platforms::pumpfun::sample::{CREATION_SLOT, CREATION_TIME, signature};
+    use solana_sdk::commitment_config::CommitmentConfig;
+    use crate::platforms::pumpfun::pumpfun::TRADE_DISCRIMINATOR;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+    use solana_transaction_status_client_types::{UiConfirmedBlock, UiTransactionEncoding};
+
+    fn options(window: AnalysisWindow) -> AnalysisOptions {
+        AnalysisOptions {
+            tx_config: RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+            window,
+            history: HistoryLimit::default(),
+            retry: RetryPolicy {
+                max_retries: 0,
+                ..RetryPolicy::default()
+            },
+            block_lookups: 20,
+            snipers: SniperOptions::default(),
+            bundles: BundleOptions::default(),
+            dev: DevOptions::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn analyses_a_pumpfun_launch() {
+        let launch = SampleLaunch::new();
+        let rpc = launch.rpc();
+
+        let analysis = run_analysis(&rpc, None, launch.mint, &options(AnalysisWindow::Full)).await.unwrap();
+
+        assert_eq!(analysis.preflight.platform, Some(Platform::PumpFun));
+        assert!(analysis.preflight.history.complete);
+        let creation = analysis.preflight.creation.as_ref().unwrap();
+        assert_eq!(creation.signature, launch.transactions[0].signature.to_string());
+        assert!(analysis.is_complete());
+
+        assert_eq!(analysis.create().map(|c| c.creator), Some(launch.creator));
+        assert_eq!(analysis.trades.len(), 5);
+        assert!(analysis.trades.windows(2).all(|w| w[0].slot <= w[1].slot));
+        assert_eq!(analysis.bonding_curve.as_ref().map(|c| c.creator), Some(launch.creator));
+
+        // Acheteurs dans les deux slots qui suivent la création
+        let snipers: Vec<Pubkey> = analysis
+            .snipers
+            .snipers
+            .iter()
+            .filter(|s| !s.is_creator)
+            .map(|s| s.wallet)
+            .collect();
+        assert!(snipers.contains(&launch.buyers[0]));
+        assert!(snipers.contains(&launch.buyers[1]));
+        assert!(!snipers.contains(&launch.buyers[2]));
+
+        let dev: &DevReport = analysis.dev.as_ref().unwrap();
+        assert_eq!(dev.wallet, launch.creator);
+        assert!(dev.initial_buy_in_create_tx);
+        assert!(dev.sells.is_empty());
+    }
+
+    #[tokio::test]
+    async fn first_window_keeps_the_earliest_transactions() {
+        let launch = SampleLaunch::new();
+        let rpc = launch.rpc();
+
+        let analysis = run_analysis(&rpc, None, launch.mint, &options(AnalysisWindow::First(2))).await.unwrap();
+
+        assert_eq!(analysis.preflight.transactions_to_analyze.len(), 2);
+        assert_eq!(analysis.creates.len(), 1);
+        let users: Vec<Pubkey> = analysis.trades.iter().map(|t| t.user).collect();
+        assert_eq!(users, vec![launch.creator, launch.buyers[0]]);
+    }
+
+    #[tokio::test]
+    async fn orders_a_shared_slot_by_block_position() {
+        let launch = SampleLaunch::new();
+        // Le snipe précède la création dans le bloc : seule la lecture du bloc le révèle
+        let create = launch.transactions[0].signature.to_string();
+        let snipe = launch.transactions[1].signature.to_string();
+        let block = UiConfirmedBlock {
+            previous_blockhash: String::new(),
+            blockhash: String::new(),
+            parent_slot: CREATION_SLOT - 1,
+            transactions: None,
+            signatures: Some(vec![signature(200).to_string(), snipe.clone(), create.clone()]),
+            rewards: None,
+            num_reward_partitions: None,
+            block_time: Some(CREATION_TIME),
+            block_height: None,
+        };
+        let rpc = launch.rpc().with_block(CREATION_SLOT, block);
+
+        let analysis = run_analysis(&rpc, None, launch.mint, &options(AnalysisWindow::Full)).await.unwrap();
+
+        let first: Vec<(&str, Option<u32>)> = analysis.trades[..2]
+            .iter()
+            .map(|t| (t.signature.as_str(), t.tx_index))
+            .collect();
+        assert_eq!(first, vec![(snipe.as_str(), Some(1)), (create.as_str(), Some(2))]);
+        assert_eq!(analysis.creates[0].tx_index, Some(2));
+    }
+
+    #[tokio::test]
+    async fn missing_transactions_are_reported_not_fatal() {
+        let launch = SampleLaunch::new();
+        let missing = &launch.transactions[2];
+        let rpc = launch
+            .transactions
+            .iter()
+            .filter(|tx| tx.signature != missing.signature)
+            .fold(launch.rpc_without_transactions(), |rpc, tx| {
+                rpc.with_transaction(&tx.signature.to_string(), tx.encoded())
+            });
+
+        let analysis = run_analysis(&rpc, None, launch.mint, &options(AnalysisWindow::Full)).await.unwrap();
+
+        assert_eq!(analysis.trades.len(), 4);
+        assert_eq!(analysis.failures.len(), 1);
+        assert_eq!(analysis.failures[0].signature, missing.signature.to_string());
+    }
+
+    #[tokio::test]
+    async fn unreadable_events_skip_their_transaction_and_are_reported() {
+        let launch = SampleLaunch::new();
+        let broken = &launch.transactions[2];
+        let mut encoded = broken.encoded();
+        // Trade tronqué : le discriminant est reconnu mais la charge ne se décode pas
+        let mut blob = TRADE_DISCRIMINATOR.to_vec();
+        blob.extend_from_slice(&[0; 12]);
+        let logs = vec![format!("Program data: {}", STANDARD.encode(blob))];
+        encoded.transaction.meta.as_mut().unwrap().log_messages = OptionSerializer::Some(logs);
+        let rpc = launch
+            .transactions
+            .iter()
+            .filter(|tx| tx.signature != broken.signature)
+            .fold(launch.rpc_without_transactions(), |rpc, tx| {
+                rpc.with_transaction(&tx.signature.to_string(), tx.encoded())
+            })
+            .with_transaction(&broken.signature.to_string(), encoded);
+
+        let analysis = run_analysis(&rpc, None, launch.mint, &options(AnalysisWindow::Full)).await.unwrap();
+
+        assert_eq!(analysis.trades.len(), 4);
+        assert!(analysis.trades.iter().all(|t| t.signature != broken.signature.to_string()));
+        assert!(!analysis.is_complete());
+        assert_eq!(analysis.failures.len(), 1);
+        assert_eq!(analysis.failures[0].signature, broken.signature.to_string());
+        assert_eq!(analysis.failures[0].kind, FailureKind::Undecodable);
+    }
+
+    #[tokio::test]
+    async fn rejects_accounts_that_are_not_mints() {
+        let launch = SampleLaunch::new();
+        let mut account = launch.mint_account();
+        account.owner = Pubkey::new_unique();
+        let rpc = launch.rpc().with_account(launch.mint, account);
+
+        let result = run_analysis(&rpc, None, launch.mint, &options(AnalysisWindow::Full)).await;
+
+        assert!(matches!(result, Err(error::Error::NotAToken(_))));
+    }
+}
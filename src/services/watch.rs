@@ -0,0 +1,316 @@
+use crate::domain::decoder::event::{DecodedEvent, decode_logs, decode_transaction, order_events};
+use crate::domain::history::HistoryLimit;
+use crate::error::{self, Result};
+use crate::infra::retry::RetryPolicy;
+use crate::infra::rpc_backend::RpcBackend;
+use crate::infra::solana_rpc::{fetch_signature_history, retrieve_transactions};
+use crate::infra::tx_cache::TxCache;
+use crate::platforms::constants::PUMPFUN_PROGRAM_ID;
+use crate::platforms::pumpfun::events::CreateEnvelope;
+use crate::platforms::pumpfun::pumpfun::PumpFun;
+use futures::StreamExt;
+use solana_client::nonblocking::pubsub_client::{PubsubClient, PubsubClientError};
+use solana_client::rpc_config::{RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+use std::time::Duration;
+
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+// Une connexion morte ne doit pas bloquer la reconnexion
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+// Signatures retenues pour ne pas émettre deux fois un lancement vu en direct puis relu au rattrapage
+const SEEN_CAPACITY: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub ws_url: String,
+    pub tx_config: RpcTransactionConfig,
+    pub retry: RetryPolicy,
+    // Signatures du programme relues au plus après une reconnexion (0 : pas de rattrapage)
+    pub backfill: usize,
+    // pump.fun émet en continu : un silence plus long signale une connexion morte
+    pub idle_timeout: Duration,
+    pub max_reconnect_delay: Duration,
+}
+
+struct RecentSignatures {
+    order: VecDeque<String>,
+    set: HashSet<String>,
+}
+
+impl RecentSignatures {
+    fn new() -> Self {
+        Self {
+            order: VecDeque::with_capacity(SEEN_CAPACITY),
+            set: HashSet::with_capacity(SEEN_CAPACITY),
+        }
+    }
+
+    fn contains(&self, signature: &str) -> bool {
+        self.set.contains(signature)
+    }
+
+    // false si la signature a déjà été vue
+    fn insert(&mut self, signature: &str) -> bool {
+        if !self.set.insert(signature.to_string()) {
+            return false;
+        }
+        self.order.push_back(signature.to_string());
+        let excess = self.order.len().saturating_sub(SEEN_CAPACITY);
+        for oldest in self.order.drain(..excess) {
+            self.set.remove(&oldest);
+        }
+        true
+    }
+}
+
+// Suit les logs du programme pump.fun et appelle `on_launch` pour chaque CreateEvent.
+// Après une coupure, on se réabonne puis on relit par getSignaturesForAddress ce qui a été manqué.
+// Ne rend la main que sur une erreur définitive (URL invalide).
+pub async fn watch_launches<F>(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    options: &WatchOptions,
+    mut on_launch: F,
+) -> Result<()>
+where
+    F: FnMut(&CreateEnvelope),
+{
+    let program = Pubkey::from_str(PUMPFUN_PROGRAM_ID).expect("Wrong address");
+    let mut seen = RecentSignatures::new();
+    let mut last_seen: Option<Signature> = None;
+    let mut delay = INITIAL_RECONNECT_DELAY;
+
+    loop {
+        let client = match PubsubClient::new(&options.ws_url).await {
+            Ok(client) => client,
+            Err(PubsubClientError::UrlParseError(e)) => return Err(error::Error::InvalidArgument(format!(
+                "invalid websocket url `{}`: {e}",
+                options.ws_url
+            ))),
+            Err(e) => {
+                tracing::warn!(retry_in = ?delay, "🔌 connexion websocket impossible: {e}");
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(options.max_reconnect_delay);
+                continue;
+            }
+        };
+
+        let filter = RpcTransactionLogsFilter::Mentions(vec![program.to_string()]);
+        let config = RpcTransactionLogsConfig {
+            commitment: Some(rpc.commitment()),
+        };
+        let (mut notifications, unsubscribe) = match client.logs_subscribe(filter, config).await {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                tracing::warn!(retry_in = ?delay, "📡 abonnement aux logs refusé: {e}");
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(options.max_reconnect_delay);
+                continue;
+            }
+        };
+        tracing::info!(url = %options.ws_url, "📡 abonné aux logs pump.fun");
+        delay = INITIAL_RECONNECT_DELAY;
+
+        // Abonné d'abord, rattrapage ensuite : rien ne peut passer entre les deux
+        if let Some(until) = last_seen {
+            backfill(rpc, cache, &program, until, options, &mut seen, &mut on_launch).await;
+        }
+
+        loop {
+            let notification = match tokio::time::timeout(options.idle_timeout, notifications.next()).await {
+                Ok(Some(notification)) => notification,
+                Ok(None) => {
+                    tracing::warn!("🔌 flux de logs fermé, reconnexion");
+                    break;
+                }
+                Err(_) => {
+                    tracing::warn!(idle = ?options.idle_timeout, "🔌 aucun log reçu, reconnexion");
+                    break;
+                }
+            };
+            let slot = notification.context.slot;
+            let logs = notification.value;
+            if let Ok(signature) = Signature::from_str(&logs.signature) {
+                last_seen = Some(signature);
+            }
+            if logs.err.is_some() || !seen.insert(&logs.signature) {
+                continue;
+            }
+            match decode_logs(&PumpFun, logs.signature.clone(), slot, &logs.logs) {
+                Ok(events) => {
+                    for event in events {
+                        if let DecodedEvent::Create(create) = event.split() {
+                            on_launch(&create);
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!(signature = %logs.signature, "événement pump.fun illisible: {e}"),
+            }
+        }
+
+        drop(notifications);
+        drop(unsubscribe);
+        if tokio::time::timeout(CLOSE_TIMEOUT, client.shutdown()).await.is_err() {
+            tracing::warn!("fermeture du websocket abandonnée");
+        }
+    }
+}
+
+// Relit les transactions du programme postérieures à `until`, les plus anciennes d'abord
+async fn backfill<F>(
+    rpc: &dyn RpcBackend,
+    cache: Option<&TxCache>,
+    program: &Pubkey,
+    until: Signature,
+    options: &WatchOptions,
+    seen: &mut RecentSignatures,
+    on_launch: &mut F,
+) where
+    F: FnMut(&CreateEnvelope),
+{
+    if options.backfill == 0 {
+        return;
+    }
+    let limit = HistoryLimit {
+        max_signatures: Some(options.backfill),
+        until_signature: Some(until),
+        ..Default::default()
+    };
+    let history = match fetch_signature_history(rpc, program, &limit).await {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::warn!("rattrapage impossible, des lancements peuvent manquer: {e}");
+            return;
+        }
+    };
+    if history.signatures.len() >= options.backfill {
+        tracing::warn!(backfill = options.backfill, "⚠️ coupure plus longue que le rattrapage, des lancements peuvent manquer");
+    }
+    let signatures: Vec<String> = history
+        .signatures
+        .iter()
+        .rev()
+        .filter(|s| s.err.is_none() && !seen.contains(&s.signature))
+        .map(|s| s.signature.clone())
+        .collect();
+    let fetched = retrieve_transactions(rpc, cache, signatures.clone(), options.tx_config, &options.retry).await;
+
+    // Une signature n'est marquée vue qu'une fois relue et décodée : sinon le direct peut encore l'émettre
+    let failed: HashSet<&str> = fetched.failures.iter().map(|f| f.signature.as_str()).collect();
+    let fetched_signatures = signatures.iter().filter(|s| !failed.contains(s.as_str()));
+    let mut launches = Vec::new();
+    for (signature, tx) in fetched_signatures.zip(&fetched.transactions) {
+        match decode_transaction(&PumpFun, tx) {
+            Ok(events) => {
+                seen.insert(signature);
+                launches.extend(events.into_iter().filter_map(|event| match event.split() {
+                    DecodedEvent::Create(create) => Some(create),
+                    DecodedEvent::Trade(_) => None,
+                }));
+            }
+            Err(e) => tracing::warn!(slot = tx.slot, "événement pump.fun illisible: {e}"),
+        }
+    }
+    order_events(&mut launches);
+    tracing::info!(
+        transactions = fetched.transactions.len(),
+        launches = launches.len(),
+        "🔁 rattrapage après reconnexion"
+    );
+    for launch in &launches {
+        on_launch(launch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::mock_pubsub::{MockPubsubServer, logs_notification};
+    use crate::platforms::pumpfun::sample::{CREATION_SLOT, CREATION_TIME, SampleLaunch, SampleTx, key, signature};
+    use solana_sdk::commitment_config::CommitmentConfig;
+    use solana_transaction_status_client_types::UiTransactionEncoding;
+    use tokio::sync::mpsc;
+
+    fn options(ws_url: String) -> WatchOptions {
+        WatchOptions {
+            ws_url,
+            tx_config: RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+            retry: RetryPolicy {
+                max_retries: 0,
+                ..RetryPolicy::default()
+            },
+            backfill: 100,
+            idle_timeout: Duration::from_secs(5),
+            max_reconnect_delay: Duration::from_secs(1),
+        }
+    }
+
+    // Transaction de création d'un autre mint, `offset` slots après le lancement d'exemple
+    fn launch_tx(launch: &SampleLaunch, seed: u8, offset: u64) -> SampleTx {
+        let mut create = launch.create().clone();
+        create.mint = key(seed);
+        SampleTx {
+            signature: signature(seed),
+            slot: CREATION_SLOT + offset,
+            block_time: CREATION_TIME + offset as i64,
+            signer: launch.creator,
+            creates: vec![create],
+            trades: Vec::new(),
+        }
+    }
+
+    fn notification(tx: &SampleTx) -> serde_json::Value {
+        logs_notification(tx.slot, &tx.signature.to_string(), &tx.logs())
+    }
+
+    #[tokio::test]
+    async fn backfills_after_a_reconnect_without_duplicates() {
+        let launch = SampleLaunch::new();
+        let program = Pubkey::from_str(PUMPFUN_PROGRAM_ID).unwrap();
+        // Dernier log vu avant la coupure, puis deux lancements manqués et un nouveau
+        let last_seen = &launch.transactions[1];
+        let unavailable = launch_tx(&launch, 41, 10);
+        let missed = launch_tx(&launch, 42, 11);
+        let live = launch_tx(&launch, 43, 12);
+        // `unavailable` n'est pas encore lisible par getTransaction au moment du rattrapage
+        let rpc = launch
+            .rpc()
+            .with_signatures(program, [last_seen.status(), unavailable.status(), missed.status()])
+            .with_transaction(&missed.signature.to_string(), missed.encoded());
+        let server = MockPubsubServer::start(vec![
+            vec![notification(last_seen)],
+            vec![notification(&unavailable), notification(&missed), notification(&live)],
+        ])
+        .await;
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let options = options(server.url());
+        let watch = watch_launches(&rpc, None, &options, |create| sender.send(create.mint).unwrap());
+        let collect = async {
+            let mut mints = Vec::new();
+            while mints.len() < 3 {
+                mints.push(receiver.recv().await.unwrap());
+            }
+            mints
+        };
+        let mints = tokio::time::timeout(Duration::from_secs(10), async {
+            tokio::select! {
+                result = watch => panic!("watch stopped: {result:?}"),
+                mints = collect => mints,
+            }
+        })
+        .await
+        .expect("three launches");
+
+        assert_eq!(server.connections(), 2);
+        // Rattrapage d'abord ; la transaction illisible au rattrapage arrive ensuite par le direct
+        assert_eq!(mints, vec![key(42), key(41), key(43)]);
+    }
+}